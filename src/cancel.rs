@@ -0,0 +1,53 @@
+use std::{cell::Cell, fmt, time::{Duration, Instant}};
+
+// Why a solver gave up instead of answering
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unsolved {
+    NoSolution,
+    TimedOut,
+    // the answer, or something on the way to it, is too big for the solver's types
+    Overflow,
+}
+
+impl fmt::Display for Unsolved {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Unsolved::NoSolution => "no solution",
+            Unsolved::TimedOut   => "timed out",
+            Unsolved::Overflow   => "overflowed",
+        })
+    }
+}
+
+thread_local! {
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+// Puts the enclosing deadline back even if the solver panics
+struct Restore(Option<Instant>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        DEADLINE.set(self.0);
+    }
+}
+
+// Run `f` with a deadline that long-running loops notice through `check`. Nested timeouts can only tighten it
+pub fn with_timeout<T>(timeout: Option<Duration>, f: impl FnOnce() -> T) -> T {
+    let outer = DEADLINE.get();
+    let _restore = Restore(outer);
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    DEADLINE.set(match (outer, deadline) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    });
+    f()
+}
+
+// Solvers call this once per round/minute/step of anything that might not finish
+pub fn check() -> Result<(), Unsolved> {
+    match DEADLINE.get() {
+        Some(deadline) if Instant::now() >= deadline => Err(Unsolved::TimedOut),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,67 @@
+use std::{fmt::Debug, ops::RangeInclusive, panic::{self, AssertUnwindSafe}};
+
+use crate::generate::Rng;
+
+// Run a fast solver and a brute-force reference over `cases` generated inputs, failing on the first disagreement
+// with that input shrunk as far as it will go. The reference can decline an input (None) if it's out of its depth
+pub fn differential<T: PartialEq + Debug>(
+    generate: impl Fn(&mut Rng, usize) -> String,
+    sizes: RangeInclusive<usize>,
+    cases: u64,
+    fast: impl Fn(&str) -> T,
+    reference: impl Fn(&str) -> Option<T>,
+) {
+    for seed in 0..cases {
+        let rng = &mut Rng::new(seed);
+        let size = sizes.start() + rng.below(sizes.end() - sizes.start() + 1);
+        let input = generate(rng, size);
+        let Some(expected) = reference(&input) else { continue; };
+        if fast(&input) != expected {
+            let shrunk = shrink(&input, |candidate| disagree(&fast, &reference, candidate));
+            panic!(
+                "seed {seed} size {size}: fast solver gave {:?}, reference gave {:?} for\n{shrunk}",
+                fast(&shrunk),
+                reference(&shrunk).unwrap(),
+            );
+        }
+    }
+}
+
+// Inputs that fall apart part way through shrinking (panics, or the reference declining) don't count
+fn disagree<T: PartialEq>(fast: impl Fn(&str) -> T, reference: impl Fn(&str) -> Option<T>, input: &str) -> bool {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        reference(input).map(|expected| fast(input) != expected).unwrap_or(false)
+    })).unwrap_or(false)
+}
+
+// Delta debugging: keep dropping chunks of lines (or characters, for one-line inputs) while the input still fails,
+// halving the chunk size once nothing more can go
+pub fn shrink(input: &str, still_failing: impl Fn(&str) -> bool) -> String {
+    let multiline = input.trim_end().contains('\n');
+    let mut units: Vec<String> = if multiline {
+        input.lines().map(str::to_owned).collect()
+    } else {
+        input.trim_end().chars().map(String::from).collect()
+    };
+    let join = |units: &[String]| if multiline { units.join("\n") + "\n" } else { units.concat() + "\n" };
+
+    let mut chunk = units.len() / 2;
+    while chunk > 0 {
+        let mut removed_any = false;
+        let mut start = 0;
+        while start < units.len() {
+            let end = (start + chunk).min(units.len());
+            let candidate: Vec<String> = units[..start].iter().chain(&units[end..]).cloned().collect();
+            if still_failing(&join(&candidate)) {
+                units = candidate;
+                removed_any = true;
+            } else {
+                start = end;
+            }
+        }
+        if !removed_any {
+            chunk /= 2;
+        }
+    }
+    join(&units)
+}
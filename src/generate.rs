@@ -0,0 +1,72 @@
+use std::ops::RangeInclusive;
+
+// splitmix64, so a seed always produces the same input
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // uniform in 0..n
+    pub fn below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let width = (range.end() - range.start()) as u64 + 1;
+        range.start() + ((self.next_u64() as u128 * width as u128) >> 64) as i64
+    }
+
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64) < p * (1u64 << 53) as f64
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+
+    pub fn lowercase(&mut self, len: usize) -> String {
+        (0..len).map(|_| (b'a' + self.below(26) as u8) as char).collect()
+    }
+}
+
+// What `size` means is up to each day: elves, rounds, sensors, useful valves...
+pub fn generate(year: u32, day: u32, size: usize, seed: u64) -> Option<String> {
+    let rng = &mut Rng::new(seed);
+    let input = match (year, day) {
+        (2022, 1)  => crate::y2022::day_1::generate(rng, size),
+        (2022, 2)  => crate::y2022::day_2::generate(rng, size),
+        (2022, 3)  => crate::y2022::day_3::generate(rng, size),
+        (2022, 4)  => crate::y2022::day_4::generate(rng, size),
+        (2022, 6)  => crate::y2022::day_6::generate(rng, size),
+        (2022, 7)  => crate::y2022::day_7::generate(rng, size),
+        (2022, 12) => crate::y2022::day_12::generate(rng, size),
+        (2022, 13) => crate::y2022::day_13::generate(rng, size),
+        (2022, 14) => crate::y2022::day_14::generate(rng, size),
+        (2022, 15) => crate::y2022::day_15::generate(rng, size),
+        (2022, 16) => crate::y2022::day_16::generate(rng, size),
+        (2022, 17) => crate::y2022::day_17::generate(rng, size),
+        (2022, 18) => crate::y2022::day_18::generate(rng, size),
+        (2022, 20) => crate::y2022::day_20::generate(rng, size),
+        (2022, 21) => crate::y2022::day_21::generate(rng, size),
+        (2022, 23) => crate::y2022::day_23::generate(rng, size),
+        (2022, 24) => crate::y2022::day_24::generate(rng, size),
+        _          => return None,
+    };
+    Some(input)
+}
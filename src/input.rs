@@ -0,0 +1,61 @@
+// A puzzle input tidied up so that it doesn't matter where it was saved: no byte order mark, \n line endings, nothing
+// trailing at the end of a line and no blank lines at the end
+pub struct Input {
+    text: String,
+}
+
+impl Input {
+    pub fn new(raw: &str) -> Input {
+        let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+        let raw = raw.replace("\r\n", "\n").replace('\r', "\n");
+        let lines: Vec<&str> = raw.split('\n').map(str::trim_end).collect();
+        let text = lines.join("\n").trim_end_matches('\n').to_owned();
+        Input { text }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+
+    // Runs of lines separated by one or more blank lines
+    pub fn blocks(&self) -> impl Iterator<Item = &str> {
+        self.text.split("\n\n").map(|block| block.trim_matches('\n')).filter(|block| !block.is_empty())
+    }
+
+    // Every character with its column and row
+    pub fn grid(&self) -> impl Iterator<Item = (usize, usize, char)> + '_ {
+        self.lines().enumerate().flat_map(|(y, line)| line.chars().enumerate().map(move |(x, c)| (x, y, c)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{self, Options, DAYS};
+
+    #[test]
+    fn normalises_line_endings_and_ends() {
+        let input = Input::new("\u{feff}1 2  \r\n3\r\r\n\r\n4\n\n\n\n5\t\r\n\r\n\n");
+        assert_eq!(input.text(), "1 2\n3\n\n\n4\n\n\n\n5");
+        assert_eq!(input.blocks().collect::<Vec<_>>(), ["1 2\n3", "4", "5"]);
+        assert_eq!(input.lines().count(), 9);
+        assert_eq!(Input::new("ab\r\nc").grid().collect::<Vec<_>>(), [(0, 0, 'a'), (1, 0, 'b'), (0, 1, 'c')]);
+    }
+
+    // Every day's sample, saved the way Windows would save it
+    #[test]
+    fn every_day_reads_windows_inputs() {
+        for day in DAYS {
+            let options = Options { day, sample: true, input: None, overrides: vec![], stats: false, timeout: None };
+            let unix = std::fs::read_to_string(runner::input_path(day.year, day.number, true)).unwrap();
+            let windows = format!("\u{feff}{}", unix.replace('\n', "\r\n"));
+            for part in 1..=2 {
+                assert_eq!(options.solve(part, &windows), options.solve(part, &unix), "day {} part {part}", day.number);
+            }
+        }
+    }
+}
@@ -1,22 +1,284 @@
+use std::{env, fs::{self, File}, io::{self, BufReader, BufWriter, IsTerminal, stdin, stdout}, net::TcpListener, process, time::Duration};
+
+use render::{Colour, Format, Frame};
+use runner::Options;
+
+#[global_allocator]
+static ALLOCATOR: memory::Counting = memory::Counting;
+
 fn main() {
-    println!("{:?}", day_24::part_1());
-    println!("{:?}", day_24::part_2());
-}
-
-mod day_1;
-mod day_2;
-mod day_3;
-mod day_4;
-mod day_6;
-mod day_7;
-mod day_12;
-mod day_13;
-mod day_14;
-mod day_15;
-mod day_16;
-mod day_17;
-mod day_18;
-mod day_20;
-mod day_21;
-mod day_23;
-mod day_24;
\ No newline at end of file
+    // -v, -vv and so on can go anywhere, each v turning up the tracing by one level
+    let (flags, args): (Vec<String>, Vec<String>) = env::args().skip(1)
+        .partition(|arg| arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v'));
+    let verbosity = flags.iter().map(|flag| flag.len() - 1).sum::<usize>();
+    trace::init(verbosity.min(u8::MAX as usize) as u8).unwrap_or_else(|e| fail(&e));
+    // nobody's watching if the answers are going to a file or a pipe
+    if stdout().is_terminal() {
+        progress::set_hook(Some(progress::draw_on_stderr));
+    }
+
+    match args.first().map(String::as_str) {
+        Some("render") => render(&args[1..]),
+        Some("generate") => generate(&args[1..]),
+        Some("run") => run(&args[1..]),
+        Some("watch") => watch(&args[1..]),
+        Some("new-day") => new_day(&args[1..]),
+        Some("report") => report(&args[1..]),
+        Some("serve") => serve(&args[1..]),
+        Some("stats") => stats(&args[1..]),
+        Some("cipher") => cipher(&args[1..]),
+        Some("model") => model(&args[1..]),
+        Some("camp") => camp(&args[1..]),
+        Some("markers") => markers(&args[1..]),
+        _ => run(&args),
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{message}");
+    process::exit(1);
+}
+
+// Pull `--year Y` out of a command's arguments, defaulting to the latest year with anything solved
+fn take_year(args: &[String]) -> (u32, Vec<String>) {
+    let Some(at) = args.iter().position(|arg| arg == "--year") else {
+        return (runner::latest_year(), args.to_vec());
+    };
+    let Some(year) = args.get(at + 1) else {
+        fail("--year needs a value");
+    };
+    let year = year.parse().unwrap_or_else(|_| fail(&format!("not a year: {year}")));
+    (year, args[..at].iter().chain(&args[at + 2..]).cloned().collect())
+}
+
+// run [--year Y] [--day N] [--sample] [--input path] [--param key=value]... [--stats] [--timeout seconds]
+fn run(args: &[String]) {
+    let options = Options::from_args(args).unwrap_or_else(|e| fail(&e));
+    let path = options.path();
+    let input = fs::read_to_string(&path).unwrap_or_else(|e| fail(&format!("{path}: {e}")));
+    trace::info!("day {} of {} on {path}", options.day.number, options.day.year);
+    for part in 1..=2 {
+        let (answer, stats) = options.solve_with_stats(part, &input);
+        println!("{}", answer.unwrap_or_else(|e| fail(&e)));
+        if options.stats {
+            eprintln!("part {part}: {stats}");
+        }
+    }
+}
+
+// watch [--year Y] [--day N] [--sample] [--input path] [--param key=value]... [--stats] [--timeout seconds]
+fn watch(args: &[String]) {
+    let options = Options::from_args(args).unwrap_or_else(|e| fail(&e));
+    watch::watch(&options);
+}
+
+// report [--year Y] [--sample] [--timeout seconds] [output.md]
+fn report(args: &[String]) {
+    let (year, args) = take_year(args);
+    let mut sample = false;
+    let mut timeout = None;
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--sample" => sample = true,
+            "--timeout" => {
+                let seconds = args.next().and_then(|s| s.parse().ok()).unwrap_or_else(|| fail("--timeout needs a number of seconds"));
+                timeout = Some(Duration::try_from_secs_f64(seconds).unwrap_or_else(|e| fail(&format!("--timeout: {e}"))));
+            }
+            _ if output.is_none() && !arg.starts_with("--") => output = Some(arg),
+            _ => fail("usage: report [--year Y] [--sample] [--timeout seconds] [output.md]"),
+        }
+    }
+    let markdown = report::report(year, sample, timeout);
+    match output {
+        Some(path) => fs::write(path, markdown).unwrap_or_else(|e| fail(&format!("{path}: {e}"))),
+        None => print!("{markdown}"),
+    }
+}
+
+// stats [--sample | input], for day 1's calorie inventory
+fn stats(args: &[String]) {
+    let inventory = match args {
+        [] => y2022::day_1::inventory(y2022::day_1::real_input()),
+        [flag] if flag == "--sample" => y2022::day_1::inventory(BufReader::new(open(&runner::input_path(2022, 1, true)))),
+        [path] => y2022::day_1::inventory(BufReader::new(open(path))),
+        _ => fail("usage: stats [--sample | input]"),
+    };
+    print!("{}", inventory.unwrap_or_else(|e| fail(&e.to_string())));
+}
+
+// cipher [--sample | input] [--expect score] [--game rps | rpsls | table], for day 2's strategy guide read every way
+fn cipher(args: &[String]) {
+    let usage = "usage: cipher [--sample | input] [--expect score] [--game rps | rpsls | table]";
+    let mut path = runner::input_path(2022, 2, false);
+    let mut expected = None;
+    let mut game = y2022::day_2::Game::rock_paper_scissors();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--sample" => path = runner::input_path(2022, 2, true),
+            "--expect" => expected = Some(args.next().and_then(|s| s.parse().ok()).unwrap_or_else(|| fail("--expect needs a score"))),
+            "--game" => game = args.next().unwrap_or_else(|| fail("--game needs a value")).parse().unwrap_or_else(|e: String| fail(&e)),
+            _ if !arg.starts_with("--") => path = arg.clone(),
+            _ => fail(usage),
+        }
+    }
+    let input = fs::read_to_string(&path).unwrap_or_else(|e| fail(&format!("{path}: {e}")));
+    print!("{}", y2022::day_2::decode(&input, &game, expected));
+}
+
+// model [--sample | input] [--game rps | rpsls | table] [--matches N] [--seed S], for day 2's opponent
+fn model(args: &[String]) {
+    let usage = "usage: model [--sample | input] [--game rps | rpsls | table] [--matches N] [--seed S]";
+    let mut path = runner::input_path(2022, 2, false);
+    let mut game = y2022::day_2::Game::rock_paper_scissors();
+    let mut matches = 1000;
+    let mut seed = 0;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--sample" => path = runner::input_path(2022, 2, true),
+            "--game" => game = args.next().unwrap_or_else(|| fail("--game needs a value")).parse().unwrap_or_else(|e: String| fail(&e)),
+            "--matches" => matches = args.next().and_then(|s| s.parse().ok()).unwrap_or_else(|| fail("--matches needs a number")),
+            "--seed" => seed = args.next().and_then(|s| s.parse().ok()).unwrap_or_else(|| fail("--seed needs a number")),
+            _ if !arg.starts_with("--") => path = arg.clone(),
+            _ => fail(usage),
+        }
+    }
+    let input = fs::read_to_string(&path).unwrap_or_else(|e| fail(&format!("{path}: {e}")));
+    print!("{}", y2022::day_2::model(&input, &game, matches, seed).unwrap_or_else(|e| fail(&e.to_string())));
+}
+
+// camp [--sample | input], for day 4's sections across the whole camp and how the pairs' assignments relate
+fn camp(args: &[String]) {
+    let path = match args {
+        [] => runner::input_path(2022, 4, false),
+        [flag] if flag == "--sample" => runner::input_path(2022, 4, true),
+        [path] if !path.starts_with("--") => path.clone(),
+        _ => fail("usage: camp [--sample | input]"),
+    };
+    let input = fs::read_to_string(&path).unwrap_or_else(|e| fail(&format!("{path}: {e}")));
+    print!("{}", y2022::day_4::coverage_from_str(&input));
+    print!("{}", y2022::day_4::relations_from_str(&input));
+}
+
+// markers [--size N] [--sample | input | -], every one of day 6's markers as the datastream goes by
+fn markers(args: &[String]) {
+    let mut size = 4;
+    let mut path = runner::input_path(2022, 6, false);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => size = args.next().and_then(|s| s.parse().ok()).filter(|&size| size > 0).unwrap_or_else(|| fail("--size needs a number above 0")),
+            "--sample" => path = runner::input_path(2022, 6, true),
+            _ if !arg.starts_with("--") => path = arg.clone(),
+            _ => fail("usage: markers [--size N] [--sample | input | -]"),
+        }
+    }
+    let print = |offset: io::Result<usize>| println!("{}", offset.unwrap_or_else(|e| fail(&format!("{path}: {e}"))));
+    if path == "-" {
+        y2022::day_6::markers(stdin().lock(), size).for_each(print);
+    } else {
+        y2022::day_6::markers(open(&path), size).for_each(print);
+    }
+}
+
+fn open(path: &str) -> File {
+    File::open(path).unwrap_or_else(|e| fail(&format!("{path}: {e}")))
+}
+
+// serve [--port N] [--timeout seconds]
+fn serve(args: &[String]) {
+    let mut port = 8022;
+    let mut timeout = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| fail(&format!("{arg} needs a value")));
+        match arg.as_str() {
+            "--port" => port = value().parse().unwrap_or_else(|_| fail("--port needs a port number")),
+            "--timeout" => {
+                let seconds = value().parse().unwrap_or_else(|_| fail("--timeout needs a number of seconds"));
+                timeout = Some(Duration::try_from_secs_f64(seconds).unwrap_or_else(|e| fail(&format!("--timeout: {e}"))));
+            }
+            _ => fail("usage: serve [--port N] [--timeout seconds]"),
+        }
+    }
+    // only ever on localhost: anyone who can reach it can keep it busy
+    let listener = TcpListener::bind(("127.0.0.1", port)).unwrap_or_else(|e| fail(&format!("port {port}: {e}")));
+    println!("listening on http://127.0.0.1:{port}");
+    runner::capture_panics();
+    serve::serve(listener, timeout);
+}
+
+// new-day [--year Y] <day>
+fn new_day(args: &[String]) {
+    let (year, args) = take_year(args);
+    let [day] = &args[..] else {
+        fail("usage: new-day [--year Y] <day>");
+    };
+    let day = day.parse().unwrap_or_else(|_| fail(&format!("not a number: {day}")));
+    for step in scaffold::new_day(year, day).unwrap_or_else(|e| fail(&e)) {
+        println!("{step}");
+    }
+}
+
+// generate [--year Y] <day> <size> [seed]
+fn generate(args: &[String]) {
+    let (year, args) = take_year(args);
+    let [day, size, rest @ ..] = &args[..] else {
+        fail("usage: generate [--year Y] <day> <size> [seed]");
+    };
+    let number = |arg: &String| arg.parse::<u64>().unwrap_or_else(|_| fail(&format!("not a number: {arg}")));
+    let seed = rest.first().map(number).unwrap_or(0);
+    let Some(input) = generate::generate(year, number(day) as u32, number(size) as usize, seed) else {
+        fail(&format!("day {day} of {year} has no generator"));
+    };
+    print!("{input}");
+}
+
+// render [--year Y] <day> <input> <output.ppm|png|gif> [scale] [frames]
+fn render(args: &[String]) {
+    let (year, args) = take_year(args);
+    let [day, input, output, rest @ ..] = &args[..] else {
+        fail("usage: render [--year Y] <day> <input> <output.ppm|png|gif> [scale] [frames]");
+    };
+    let number = |arg: Option<&String>, default| arg
+        .map(|a| a.parse().unwrap_or_else(|_| fail(&format!("not a number: {a}"))))
+        .unwrap_or(default);
+    let scale = number(rest.first(), 4);
+    let count = number(rest.get(1), 200);
+
+    let (frames, palette): (Vec<Frame>, &[Colour]) = match (year, day.as_str()) {
+        (2022, "12") => (y2022::day_12::frames(input, count), &y2022::day_12::PALETTE),
+        (2022, "14") => (y2022::day_14::frames(input, count), &y2022::day_14::PALETTE),
+        (2022, "17") => (y2022::day_17::frames(input, count), &y2022::day_17::PALETTE),
+        (2022, "23") => (y2022::day_23::frames(input, count), &y2022::day_23::PALETTE),
+        (2022, "24") => (y2022::day_24::frames(input, count), &y2022::day_24::PALETTE),
+        _            => fail(&format!("day {day} of {year} has nothing to render")),
+    };
+    let Some(format) = Format::from_path(output) else {
+        fail("output must end in .ppm, .png or .gif");
+    };
+
+    let mut file = BufWriter::new(File::create(output).unwrap_or_else(|e| fail(&e.to_string())));
+    render::write(&mut file, format, &frames, palette, scale).unwrap_or_else(|e| fail(&e.to_string()));
+}
+
+mod cancel;
+#[cfg(test)]
+mod check;
+mod generate;
+mod input;
+mod memory;
+mod params;
+mod progress;
+mod render;
+mod report;
+mod runner;
+mod scaffold;
+mod serve;
+mod trace;
+mod watch;
+
+mod y2022;
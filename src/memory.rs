@@ -0,0 +1,133 @@
+use std::{alloc::{GlobalAlloc, Layout, System}, fmt, sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering}};
+
+// Hands everything to the system allocator, keeping count only while a measurement is running so it costs one atomic
+// load per allocation the rest of the time
+pub struct Counting;
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+// relative to the start of the measurement, so frees of older memory can take it below zero
+static LIVE: AtomicI64 = AtomicI64::new(0);
+static PEAK: AtomicI64 = AtomicI64::new(0);
+
+fn allocated(size: usize) {
+    if COUNTING.load(Ordering::Relaxed) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size as i64, Ordering::Relaxed) + size as i64;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+}
+
+fn freed(size: usize) {
+    if COUNTING.load(Ordering::Relaxed) {
+        LIVE.fetch_sub(size as i64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc(layout);
+        if !p.is_null() {
+            allocated(layout.size());
+        }
+        p
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc_zeroed(layout);
+        if !p.is_null() {
+            allocated(layout.size());
+        }
+        p
+    }
+
+    unsafe fn dealloc(&self, p: *mut u8, layout: Layout) {
+        System.dealloc(p, layout);
+        freed(layout.size());
+    }
+
+    // a Vec or HashMap growing counts as a fresh allocation of the new size
+    unsafe fn realloc(&self, p: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_p = System.realloc(p, layout, new_size);
+        if !new_p.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        new_p
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub allocations: u64,
+    pub bytes: u64,
+    pub peak: u64,
+}
+
+impl fmt::Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} allocations, {} allocated, {} peak", self.allocations, Bytes(self.bytes), Bytes(self.peak))
+    }
+}
+
+pub struct Bytes(pub u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut size = self.0 as f64 / 1024.0;
+        for unit in ["KiB", "MiB"] {
+            if size < 1024.0 {
+                return write!(f, "{size:.1} {unit}");
+            }
+            size /= 1024.0;
+        }
+        write!(f, "{size:.1} GiB")
+    }
+}
+
+// Stops the counting even if the measured code panics
+struct StopCounting;
+
+impl Drop for StopCounting {
+    fn drop(&mut self) {
+        COUNTING.store(false, Ordering::Relaxed);
+    }
+}
+
+// Everything allocated while `f` runs. Measurements don't nest, and other threads allocating meanwhile get counted too
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+    LIVE.store(0, Ordering::Relaxed);
+    PEAK.store(0, Ordering::Relaxed);
+    COUNTING.store(true, Ordering::Relaxed);
+    let result = {
+        let _stop = StopCounting;
+        f()
+    };
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+        peak: PEAK.load(Ordering::Relaxed).max(0) as u64,
+    };
+    (result, usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+
+    use super::*;
+
+    #[test]
+    fn a_panic_stops_the_counting() {
+        let panicked = panic::catch_unwind(|| measure(|| panic!("solver fell over")));
+        assert!(panicked.is_err());
+        assert!(!COUNTING.load(Ordering::Relaxed));
+    }
+}
@@ -0,0 +1,46 @@
+use std::{fmt::{Debug, Display}, str::FromStr};
+
+// Puzzle constants that differ between the real input and the worked example, or that we want to play with
+pub trait Params: Default + Debug {
+    fn sample() -> Self;
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String>;
+}
+
+pub fn parse<T: FromStr<Err: Display>>(key: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|e| format!("bad value for {key}: {value} ({e})"))
+}
+
+// Declares a day's `Params` struct, giving each field its real puzzle default and its value for the sample input
+macro_rules! params {
+    ($($name:ident: $ty:ty = $real:expr, $sample:expr;)*) => {
+        #[derive(Clone, Debug)]
+        pub struct Params {
+            $(pub $name: $ty,)*
+        }
+
+        impl Default for Params {
+            fn default() -> Self {
+                Params { $($name: $real,)* }
+            }
+        }
+
+        impl crate::params::Params for Params {
+            fn sample() -> Self {
+                Params { $($name: $sample,)* }
+            }
+
+            fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+                match key {
+                    $(stringify!($name) => self.$name = crate::params::parse(key, value)?,)*
+                    _ => return Err(format!(
+                        "unknown parameter {key}, expected one of: {}",
+                        [$(stringify!($name)),*].join(", "),
+                    )),
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+pub(crate) use params;
@@ -0,0 +1,190 @@
+use std::{cell::RefCell, io::{self, Write}, sync::{RwLock, atomic::{AtomicBool, Ordering}}, time::{Duration, Instant}};
+
+// Where a long search has got to, handed to whatever hook is installed
+pub struct Report<'a> {
+    pub label: &'a str,
+    pub done: u64,
+    pub total: Option<u64>,
+    pub explored: u64,
+    pub elapsed: Duration,
+}
+
+impl Report<'_> {
+    // Straight-line guess from the rate so far, when we know how many steps there are
+    pub fn remaining(&self) -> Option<Duration> {
+        let total = self.total?;
+        if self.done == 0 || self.done > total {
+            return None;
+        }
+        Some(self.elapsed.mul_f64((total - self.done) as f64 / self.done as f64))
+    }
+}
+
+// Called with Some while a task is running and None once it has finished
+pub type Hook = fn(Option<&Report>);
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static HOOK: RwLock<Option<Hook>> = RwLock::new(None);
+
+const REPORT_EVERY: Duration = Duration::from_millis(100);
+// `explored` gets called from the middle of deep recursions, so it only looks at the clock this often
+const CALLS_BETWEEN_CLOCK_CHECKS: u32 = 1024;
+
+pub fn set_hook(hook: Option<Hook>) {
+    *HOOK.write().unwrap() = hook;
+    ENABLED.store(hook.is_some(), Ordering::Relaxed);
+}
+
+struct Task {
+    label: &'static str,
+    done: u64,
+    total: Option<u64>,
+    explored: u64,
+    started: Instant,
+    last_report: Instant,
+    calls: u32,
+}
+
+thread_local! {
+    static TASK: RefCell<Option<Task>> = const { RefCell::new(None) };
+}
+
+// Ends the task when dropped, however the solver leaves
+pub struct Guard(bool);
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        if self.0 && TASK.with(|task| task.borrow_mut().take()).is_some() {
+            if let Some(hook) = *HOOK.read().unwrap() {
+                hook(None);
+            }
+        }
+    }
+}
+
+// Start reporting on a search of `total` steps, if we know how many. Tasks don't nest: a new one replaces the old
+pub fn begin(label: &'static str, total: Option<u64>) -> Guard {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return Guard(false);
+    }
+    let now = Instant::now();
+    TASK.with(|task| *task.borrow_mut() = Some(Task {
+        label, done: 0, total, explored: 0, started: now, last_report: now, calls: 0,
+    }));
+    Guard(true)
+}
+
+fn update(f: impl FnOnce(&mut Task), every_call: bool) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    TASK.with(|task| {
+        let mut task = task.borrow_mut();
+        let Some(task) = task.as_mut() else { return; };
+        f(task);
+        if !every_call {
+            task.calls += 1;
+            if task.calls < CALLS_BETWEEN_CLOCK_CHECKS {
+                return;
+            }
+            task.calls = 0;
+        }
+        let now = Instant::now();
+        if now - task.last_report < REPORT_EVERY {
+            return;
+        }
+        task.last_report = now;
+        if let Some(hook) = *HOOK.read().unwrap() {
+            hook(Some(&Report {
+                label: task.label,
+                done: task.done,
+                total: task.total,
+                explored: task.explored,
+                elapsed: now - task.started,
+            }));
+        }
+    });
+}
+
+// Steps are rounds, rocks, minutes: whatever `total` counts
+pub fn advance(steps: u64) {
+    update(|task| task.done += steps, true);
+}
+
+// States are the things a search looks at along the way, however many steps they take
+pub fn explored(states: u64) {
+    update(|task| task.explored += states, false);
+}
+
+// A hook that keeps rewriting one line of the terminal
+pub fn draw_on_stderr(report: Option<&Report>) {
+    let mut stderr = io::stderr().lock();
+    let _ = match report {
+        None => write!(stderr, "\r\x1b[2K"),
+        Some(report) => {
+            let mut line = format!("{}: {}", report.label, report.done);
+            if let Some(total) = report.total {
+                line += &format!("/{total}");
+            }
+            if report.explored > 0 {
+                line += &format!(", {} states explored", report.explored);
+            }
+            line += &format!(", {:.0?} so far", report.elapsed);
+            if let Some(remaining) = report.remaining() {
+                line += &format!(", about {remaining:.0?} to go");
+            }
+            write!(stderr, "\r\x1b[2K{line}")
+        }
+    };
+    let _ = stderr.flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    thread_local! {
+        // (done, explored) for every report this thread's hook saw, None when a task finished
+        static SEEN: RefCell<Vec<Option<(u64, u64)>>> = const { RefCell::new(vec![]) };
+    }
+
+    // Stands in for draw_on_stderr. Other tests' tasks run on their own threads, so they never show up here
+    fn record(report: Option<&Report>) {
+        SEEN.with(|seen| seen.borrow_mut().push(report.map(|report| (report.done, report.explored))));
+    }
+
+    #[test]
+    fn remaining_scales_the_time_so_far() {
+        let report = |done, total| Report { label: "test", done, total, explored: 0, elapsed: Duration::from_secs(4) };
+        assert_eq!(report(2, Some(10)).remaining(), Some(Duration::from_secs(16)));
+        assert_eq!(report(10, Some(10)).remaining(), Some(Duration::ZERO));
+        assert_eq!(report(0, Some(10)).remaining(), None);
+        assert_eq!(report(11, Some(10)).remaining(), None);
+        assert_eq!(report(2, None).remaining(), None);
+    }
+
+    #[test]
+    fn redraws_are_throttled() {
+        set_hook(Some(record));
+        {
+            let _task = begin("test", Some(10));
+            advance(1);
+            advance(1);
+            thread::sleep(REPORT_EVERY);
+            advance(1);
+            advance(1);
+            // explored only looks at the clock every so many calls, however long it's been
+            thread::sleep(REPORT_EVERY);
+            for _ in 1..CALLS_BETWEEN_CLOCK_CHECKS {
+                explored(1);
+            }
+            assert_eq!(SEEN.with(|seen| seen.borrow().len()), 1);
+            explored(1);
+        }
+        set_hook(None);
+        advance(1);
+        assert_eq!(SEEN.take(), [Some((3, 0)), Some((4, u64::from(CALLS_BETWEEN_CLOCK_CHECKS))), None]);
+    }
+}
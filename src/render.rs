@@ -0,0 +1,410 @@
+use std::{collections::HashMap, io::{self, Write}};
+
+pub type Colour = [u8; 3];
+
+// A grid of palette indices, one per cell, stored row by row
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<u8>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize) -> Self {
+        Frame { width, height, cells: vec![0; width * height] }
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, cell: u8) {
+        self.cells[y * self.width + x] = cell;
+    }
+
+    // Blow every cell up into a scale x scale block of pixels
+    fn pixels(&self, scale: usize) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity(self.cells.len() * scale * scale);
+        for row in self.cells.chunks(self.width) {
+            let scaled_row: Vec<u8> = row.iter()
+                .flat_map(|&cell| std::iter::repeat_n(cell, scale))
+                .collect();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&scaled_row);
+            }
+        }
+        pixels
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Format { Ppm, Png, Gif }
+
+impl Format {
+    pub fn from_path(path: &str) -> Option<Format> {
+        match path.rsplit_once('.')?.1 {
+            "ppm" => Some(Format::Ppm),
+            "png" => Some(Format::Png),
+            "gif" => Some(Format::Gif),
+            _     => None,
+        }
+    }
+}
+
+// Stills get the last frame, animations get all of them
+pub fn write(w: &mut impl Write, format: Format, frames: &[Frame], palette: &[Colour], scale: usize) -> io::Result<()> {
+    let Some(last) = frames.last() else {
+        return Err(invalid_input("nothing to render"));
+    };
+    match format {
+        Format::Ppm => write_ppm(w, last, palette, scale),
+        Format::Png => write_png(w, last, palette, scale),
+        Format::Gif => write_gif(w, frames, palette, scale, 10),
+    }
+}
+
+fn invalid_input(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+fn check_palette(frame: &Frame, palette: &[Colour]) -> io::Result<()> {
+    if palette.is_empty() || palette.len() > 256 {
+        return Err(invalid_input("palette must have between 1 and 256 colours"));
+    }
+    if frame.cells.iter().any(|&cell| cell as usize >= palette.len()) {
+        return Err(invalid_input("frame uses a cell type missing from the palette"));
+    }
+    Ok(())
+}
+
+// An image needs at least one pixel each way
+fn check_size(frame: &Frame, scale: usize) -> io::Result<()> {
+    if scale == 0 {
+        return Err(invalid_input("scale must be at least 1"));
+    }
+    if frame.width == 0 || frame.height == 0 {
+        return Err(invalid_input("nothing to render in an empty frame"));
+    }
+    Ok(())
+}
+
+pub fn write_ppm(w: &mut impl Write, frame: &Frame, palette: &[Colour], scale: usize) -> io::Result<()> {
+    check_size(frame, scale)?;
+    check_palette(frame, palette)?;
+    write!(w, "P6\n{} {}\n255\n", frame.width * scale, frame.height * scale)?;
+    let rgb: Vec<u8> = frame.pixels(scale).into_iter()
+        .flat_map(|p| palette[p as usize])
+        .collect();
+    w.write_all(&rgb)
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let table: Vec<u32> = (0..256).map(|n| {
+        (0..8).fold(n, |c, _| if c & 1 == 1 { 0xedb8_8320 ^ (c >> 1) } else { c >> 1 })
+    }).collect();
+    !bytes.iter().fold(!0, |c, &b| table[((c ^ b as u32) & 0xff) as usize] ^ (c >> 8))
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (a, b) = bytes.iter().fold((1u32, 0u32), |(a, b), &x| {
+        let a = (a + x as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+// zlib stream made of uncompressed deflate blocks, which every decoder has to accept
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        // the chunks above are what keep this in range
+        let len = u16::try_from(block.len()).unwrap();
+        out.push(if blocks.peek().is_none() { 1 } else { 0 });
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn write_png_chunk(w: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    w.write_all(&(data.len() as u32).to_be_bytes())?;
+    let mut crc_input = kind.to_vec();
+    crc_input.extend_from_slice(data);
+    w.write_all(&crc_input)?;
+    w.write_all(&crc32(&crc_input).to_be_bytes())
+}
+
+pub fn write_png(w: &mut impl Write, frame: &Frame, palette: &[Colour], scale: usize) -> io::Result<()> {
+    check_size(frame, scale)?;
+    check_palette(frame, palette)?;
+    // PNG keeps its dimensions in 31 bits
+    let dimension = |cells: usize| cells.checked_mul(scale).and_then(|pixels| i32::try_from(pixels).ok())
+        .ok_or_else(|| invalid_input("a PNG can be at most 2147483647 pixels across and down"));
+    let (width, height) = (dimension(frame.width)?, dimension(frame.height)?);
+
+    let mut header = vec![];
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    header.extend_from_slice(&[8, 3, 0, 0, 0]); // 8 bit, indexed colour, no interlacing
+
+    // every scanline starts with filter type 0 (none)
+    let scanlines: Vec<u8> = frame.pixels(scale)
+        .chunks(width as usize)
+        .flat_map(|row| std::iter::once(0).chain(row.iter().copied()))
+        .collect();
+
+    w.write_all(b"\x89PNG\r\n\x1a\n")?;
+    write_png_chunk(w, b"IHDR", &header)?;
+    write_png_chunk(w, b"PLTE", &palette.concat())?;
+    write_png_chunk(w, b"IDAT", &zlib_stored(&scanlines))?;
+    write_png_chunk(w, b"IEND", &[])
+}
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u32,
+}
+
+impl BitWriter {
+    fn push(&mut self, code: u16, size: u32) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+// Variable-width LZW as GIF wants it, resetting the dictionary whenever it fills up
+fn lzw(pixels: &[u8], min_code_size: u32) -> Vec<u8> {
+    let clear: u16 = 1 << min_code_size;
+    let end = clear + 1;
+    let mut next = end + 1;
+    let mut code_size = min_code_size + 1;
+    let mut dictionary: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut out = BitWriter { bytes: vec![], buffer: 0, bits: 0 };
+
+    out.push(clear, code_size);
+    let Some((&first, rest)) = pixels.split_first() else {
+        out.push(end, code_size);
+        return out.finish();
+    };
+    let mut prefix = first as u16;
+    for &pixel in rest {
+        if let Some(&code) = dictionary.get(&(prefix, pixel)) {
+            prefix = code;
+            continue;
+        }
+        out.push(prefix, code_size);
+        if next == 4096 {
+            out.push(clear, code_size);
+            dictionary.clear();
+            next = end + 1;
+            code_size = min_code_size + 1;
+        } else {
+            if next >= 1 << code_size {
+                code_size += 1;
+            }
+            dictionary.insert((prefix, pixel), next);
+            next += 1;
+        }
+        prefix = pixel as u16;
+    }
+    out.push(prefix, code_size);
+    // the decoder adds an entry after reading that last code and widens its reads if that fills the current size, so
+    // END has to go out at the width it'll be expecting
+    if next < 4096 && next >= 1 << code_size {
+        code_size += 1;
+    }
+    out.push(end, code_size);
+    out.finish()
+}
+
+// delay is in hundredths of a second between frames
+pub fn write_gif(w: &mut impl Write, frames: &[Frame], palette: &[Colour], scale: usize, delay: u16) -> io::Result<()> {
+    let Some(first) = frames.first() else {
+        return Err(invalid_input("an animation needs at least one frame"));
+    };
+    for frame in frames {
+        if (frame.width, frame.height) != (first.width, first.height) {
+            return Err(invalid_input("all frames of an animation must be the same size"));
+        }
+        check_size(frame, scale)?;
+        check_palette(frame, palette)?;
+    }
+    // GIF keeps its dimensions in 16 bits
+    let dimension = |cells: usize| cells.checked_mul(scale).and_then(|pixels| u16::try_from(pixels).ok())
+        .ok_or_else(|| invalid_input("a GIF can be at most 65535 pixels across and down"));
+    let (width, height) = (dimension(first.width)?, dimension(first.height)?);
+
+    // the colour table has to be a power of two, at least 2 entries long
+    let table_bits = (usize::BITS - (palette.len() - 1).leading_zeros()).max(1);
+    let mut colour_table = palette.concat();
+    colour_table.resize(3 << table_bits, 0);
+
+    w.write_all(b"GIF89a")?;
+    w.write_all(&width.to_le_bytes())?;
+    w.write_all(&height.to_le_bytes())?;
+    w.write_all(&[0x80 | ((table_bits as u8 - 1) << 4) | (table_bits as u8 - 1), 0, 0])?;
+    w.write_all(&colour_table)?;
+    // loop forever
+    w.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+    let min_code_size = table_bits.max(2);
+    for frame in frames {
+        w.write_all(&[0x21, 0xf9, 0x04, 0x04])?;
+        w.write_all(&delay.to_le_bytes())?;
+        w.write_all(&[0, 0])?;
+
+        w.write_all(&[0x2c, 0, 0, 0, 0])?;
+        w.write_all(&width.to_le_bytes())?;
+        w.write_all(&height.to_le_bytes())?;
+        w.write_all(&[0, min_code_size as u8])?;
+        for block in lzw(&frame.pixels(scale), min_code_size).chunks(255) {
+            w.write_all(&[block.len() as u8])?;
+            w.write_all(block)?;
+        }
+        w.write_all(&[0])?;
+    }
+    w.write_all(&[0x3b])
+}
+
+// How many steps to skip between frames so that a run of `steps` fits in `frames`
+pub fn stride(steps: usize, frames: usize) -> usize {
+    steps.div_ceil(frames.max(1)).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    // A GIF decoder's side of the LZW, reading codes until END and checking the stream stops there
+    fn unlzw(bytes: &[u8], min_code_size: u32) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let mut bit = 0;
+        let mut read = |size: u32| {
+            let code = (0..size).fold(0, |code, i| {
+                let at = bit + i as usize;
+                code | (((bytes[at / 8] >> (at % 8)) & 1) as usize) << i
+            });
+            bit += size as usize;
+            code
+        };
+        let mut pixels = vec![];
+        let mut dictionary: Vec<Vec<u8>> = vec![];
+        let mut code_size = min_code_size + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        loop {
+            let code = read(code_size);
+            if code == clear {
+                dictionary = (0..clear).map(|p| vec![p as u8]).chain([vec![], vec![]]).collect();
+                code_size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                break;
+            }
+            let entry = match (&previous, dictionary.get(code)) {
+                (_, Some(entry)) => entry.clone(),
+                (Some(previous), None) => [previous.clone(), vec![previous[0]]].concat(),
+                (None, None) => panic!("code {code} before anything's in the dictionary"),
+            };
+            pixels.extend_from_slice(&entry);
+            if let Some(previous) = previous {
+                if dictionary.len() < 4096 {
+                    dictionary.push([previous, vec![entry[0]]].concat());
+                }
+                if dictionary.len() == 1 << code_size && code_size < 12 {
+                    code_size += 1;
+                }
+            }
+            previous = Some(entry);
+        }
+        assert_eq!(bit.div_ceil(8), bytes.len(), "bytes left over after END");
+        pixels
+    }
+
+    #[test]
+    fn lzw_round_trips() {
+        let mut rng = Rng::new(0);
+        for case in 0..3000 {
+            let min_code_size = 2 + rng.below(7) as u32;
+            let colours = 1 + rng.below(1 << min_code_size);
+            let len = rng.below(if case % 10 == 0 { 20_000 } else { 600 });
+            let pixels: Vec<u8> = (0..len).map(|_| rng.below(colours) as u8).collect();
+            assert_eq!(unlzw(&lzw(&pixels, min_code_size), min_code_size), pixels, "case {case}");
+        }
+    }
+
+    #[test]
+    fn png_stores_the_scanlines() {
+        let mut frame = Frame::new(2, 2);
+        frame.set(1, 0, 1);
+        let mut png = vec![];
+        write_png(&mut png, &frame, &[[0, 0, 0], [255, 255, 255]], 2).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x04\0\0\0\x04"));
+
+        // one stored block: header, final flag, length and its complement, then the data and a checksum
+        let data: Vec<u8> = (0..70_000u32).map(|i| i as u8).collect();
+        let zlib = zlib_stored(&data);
+        let mut at = 2;
+        let mut unpacked = vec![];
+        loop {
+            let last = zlib[at] == 1;
+            let len = u16::from_le_bytes([zlib[at + 1], zlib[at + 2]]);
+            assert_eq!(!len, u16::from_le_bytes([zlib[at + 3], zlib[at + 4]]));
+            unpacked.extend_from_slice(&zlib[at + 5..at + 5 + len as usize]);
+            at += 5 + len as usize;
+            if last { break; }
+        }
+        assert_eq!(unpacked, data);
+        assert_eq!(zlib[at..], adler32(&data).to_be_bytes());
+    }
+
+    #[test]
+    fn gif_dimensions_fit_in_16_bits() {
+        let palette = [[0, 0, 0]];
+        let mut gif = vec![];
+        write_gif(&mut gif, &[Frame::new(3, 2)], &palette, 2, 10).unwrap();
+        assert_eq!(gif[6..10], [6, 0, 4, 0]);
+        assert_eq!(gif.last(), Some(&0x3b));
+        let error = write_gif(&mut vec![], &[Frame::new(65_536, 1)], &palette, 1, 10).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(write_gif(&mut vec![], &[Frame::new(1, 300)], &palette, 256, 10).is_err());
+    }
+
+    #[test]
+    fn png_dimensions_fit_in_31_bits() {
+        let palette = [[0, 0, 0]];
+        let error = write_png(&mut vec![], &Frame::new(2, 1), &palette, 1 << 30).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(write_png(&mut vec![], &Frame::new(1, 1), &palette, 1 << 31).is_err());
+    }
+
+    #[test]
+    fn every_format_needs_a_pixel() {
+        let palette = [[0, 0, 0]];
+        for format in [Format::Ppm, Format::Png, Format::Gif] {
+            for (frame, scale) in [(Frame::new(2, 2), 0), (Frame::new(0, 2), 1), (Frame::new(2, 0), 1)] {
+                let error = write(&mut vec![], format, &[frame], &palette, scale).unwrap_err();
+                assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+            }
+        }
+    }
+}
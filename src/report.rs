@@ -0,0 +1,179 @@
+use std::{fmt::Write, fs, path::Path, time::Duration};
+
+use crate::runner::{self, Options, DAYS};
+
+enum Check {
+    Pass(String),
+    Fail { got: String, expected: String },
+    Unchecked(String),
+    Error(String),
+}
+
+impl Check {
+    fn cell(&self) -> String {
+        match self {
+            Check::Pass(answer) => format!("✅ `{answer}`"),
+            Check::Fail { got, expected } => format!("❌ `{got}`, expected `{expected}`"),
+            Check::Unchecked(answer) => format!("❔ `{answer}`"),
+            Check::Error(e) => format!("💥 {}", e.replace('|', "\\|")),
+        }
+    }
+}
+
+// One day's row of the table: None for the checks means there was no input to run it on
+struct Row {
+    day: u32,
+    module: String,
+    checks: Option<[Check; 2]>,
+    elapsed: Duration,
+}
+
+impl Row {
+    // how the day shows up in the calendar
+    fn mark(&self) -> &'static str {
+        let Some(checks) = &self.checks else { return "--"; };
+        let passes = checks.iter().filter(|check| matches!(check, Check::Pass(_))).count();
+        if checks.iter().any(|check| matches!(check, Check::Fail { .. } | Check::Error(_))) {
+            "!!"
+        } else {
+            ["??", "* ", "**"][passes]
+        }
+    }
+}
+
+fn run_day(options: &Options) -> Row {
+    let (year, day) = (options.day.year, options.day.number);
+    let module = format!("src/y{year}/day_{day}.rs");
+    let Ok(input) = fs::read_to_string(options.path()) else {
+        return Row { day, module, checks: None, elapsed: Duration::ZERO };
+    };
+    let expected: Vec<String> = fs::read_to_string(runner::answers_path(year, day, options.sample))
+        .map(|answers| answers.lines().map(str::to_owned).collect())
+        .unwrap_or_default();
+
+    let mut elapsed = Duration::ZERO;
+    let checks = [1, 2].map(|part| {
+        let (answer, stats) = options.solve_catching_panics(part, &input);
+        elapsed += stats.map(|stats| stats.elapsed).unwrap_or_default();
+        match (answer, expected.get(part - 1).filter(|e| *e != "?")) {
+            (Err(e), _) => Check::Error(e),
+            (Ok(got), Some(expected)) if got == *expected => Check::Pass(got),
+            (Ok(got), Some(expected)) => Check::Fail { got, expected: expected.clone() },
+            (Ok(got), None) => Check::Unchecked(got),
+        }
+    });
+    Row { day, module, checks: Some(checks), elapsed }
+}
+
+// Five days to a line, each with its mark
+fn calendar(rows: &[Row]) -> String {
+    let mut grid = String::new();
+    for week in (1..=25).collect::<Vec<u32>>().chunks(5) {
+        let cells: Vec<String> = week.iter().map(|&day| {
+            let mark = rows.iter().find(|row| row.day == day).map(Row::mark).unwrap_or("  ");
+            format!("{day:>2} {mark}")
+        }).collect();
+        grid += cells.join("   ").trim_end();
+        grid += "\n";
+    }
+    grid
+}
+
+// Runs every solved day of `year` on its real input (or the sample) and checks the answers against the .answers files
+// next to the inputs
+pub fn report(year: u32, sample: bool, timeout: Option<Duration>) -> String {
+    runner::capture_panics();
+    let rows: Vec<Row> = DAYS.iter()
+        .filter(|day| day.year == year)
+        .map(|day| run_day(&Options { day, sample, input: None, overrides: vec![], stats: false, timeout }))
+        .collect();
+
+    let kind = if sample { "sample" } else { "real" };
+    let checked = rows.iter().flat_map(|row| row.checks.iter().flatten())
+        .filter(|check| !matches!(check, Check::Unchecked(_)))
+        .count();
+    let passed = rows.iter().flat_map(|row| row.checks.iter().flatten())
+        .filter(|check| matches!(check, Check::Pass(_)))
+        .count();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "# Advent of Code {year}\n");
+    let _ = writeln!(out, "{} of 25 days solved. On the {kind} inputs {passed} of {checked} checked parts give the expected answer.\n", rows.len());
+    let _ = writeln!(out, "```text\n{}```\n", calendar(&rows));
+    let _ = writeln!(out, "`**` both parts right, `* ` one right and one unchecked, `??` nothing to check against, `!!` a wrong answer or an error, `--` no {kind} input.\n");
+
+    let _ = writeln!(out, "| Day | Part 1 | Part 2 | Time | Module |");
+    let _ = writeln!(out, "| --: | ------ | ------ | ---: | ------ |");
+    for row in &rows {
+        let module_name = Path::new(&row.module).file_name().unwrap().to_string_lossy();
+        let (part_1, part_2, time) = match &row.checks {
+            Some([part_1, part_2]) => (part_1.cell(), part_2.cell(), format!("{:.2?}", row.elapsed)),
+            None => (format!("no {kind} input"), String::new(), String::new()),
+        };
+        let _ = writeln!(out, "| {} | {part_1} | {part_2} | {time} | [{module_name}]({}) |", row.day, row.module);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(day: u32, checks: Option<[Check; 2]>) -> Row {
+        Row { day, module: format!("src/y2022/day_{day}.rs"), checks, elapsed: Duration::ZERO }
+    }
+
+    fn pass() -> Check {
+        Check::Pass("1".to_owned())
+    }
+
+    fn unchecked() -> Check {
+        Check::Unchecked("1".to_owned())
+    }
+
+    #[test]
+    fn calendar_marks_every_kind_of_day() {
+        let rows = [
+            row(1, Some([pass(), pass()])),
+            row(2, Some([pass(), unchecked()])),
+            row(3, Some([unchecked(), unchecked()])),
+            row(4, Some([pass(), Check::Fail { got: "1".to_owned(), expected: "2".to_owned() }])),
+            row(5, Some([Check::Error("timed out".to_owned()), pass()])),
+            row(6, None),
+        ];
+        assert_eq!(calendar(&rows).lines().collect::<Vec<_>>(), [
+            " 1 **    2 *     3 ??    4 !!    5 !!",
+            " 6 --    7       8       9      10",
+            "11      12      13      14      15",
+            "16      17      18      19      20",
+            "21      22      23      24      25",
+        ]);
+    }
+
+    #[test]
+    fn cells_show_what_each_part_said() {
+        assert_eq!(Check::Pass("24000".to_owned()).cell(), "✅ `24000`");
+        assert_eq!(Check::Fail { got: "7".to_owned(), expected: "8".to_owned() }.cell(), "❌ `7`, expected `8`");
+        assert_eq!(Check::Unchecked("7".to_owned()).cell(), "❔ `7`");
+        // a | would end the table cell
+        assert_eq!(Check::Error("a | b".to_owned()).cell(), "💥 a \\| b");
+    }
+
+    #[test]
+    fn runs_a_day_against_its_answers() {
+        let day = runner::find(2022, 1).unwrap();
+        let options = |input: Option<String>| Options { day, sample: true, input, overrides: vec![], stats: false, timeout: None };
+
+        assert_eq!(run_day(&options(None)).mark(), "**");
+
+        // the sample's answers, but someone else's elves
+        let path = std::env::temp_dir().join(format!("report-test-{}.txt", std::process::id()));
+        fs::write(&path, "1\n\n2\n\n3\n").unwrap();
+        let wrong = run_day(&options(Some(path.to_string_lossy().into_owned())));
+        fs::remove_file(&path).unwrap();
+        assert_eq!(wrong.mark(), "!!");
+        assert_eq!(wrong.checks.unwrap().map(|check| check.cell()), ["❌ `3`, expected `24000`", "❌ `6`, expected `45000`"]);
+
+        assert_eq!(run_day(&options(Some("input/2022/sample/nowhere.txt".to_owned()))).mark(), "--");
+    }
+}
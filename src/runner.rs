@@ -0,0 +1,253 @@
+use std::{cell::RefCell, fmt, panic::{self, AssertUnwindSafe}, time::{Duration, Instant}};
+
+use crate::{cancel, memory::{self, Usage}, params::Params, trace::info};
+
+// One part's answer for an input, starting from the sample preset or the real defaults and applying `key=value`
+// overrides
+type Part = fn(&str, bool, &[(String, String)]) -> Result<String, String>;
+
+// Whether the sample preset or the real defaults take those overrides, without solving anything
+type CheckParams = fn(bool, &[(String, String)]) -> Result<(), String>;
+
+// Parts answer with a number, or with a Result when they can give up or turn the input down
+pub trait Answer {
+    fn answer(self) -> Result<String, String>;
+}
+
+macro_rules! number_answers {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {
+            fn answer(self) -> Result<String, String> {
+                Ok(self.to_string())
+            }
+        })*
+    };
+}
+
+number_answers!(i32, i64, u64, usize);
+
+impl<T: fmt::Display, E: fmt::Display> Answer for Result<T, E> {
+    fn answer(self) -> Result<String, String> {
+        self.map(|answer| answer.to_string()).map_err(|e| e.to_string())
+    }
+}
+
+pub struct Day {
+    pub year: u32,
+    pub number: u32,
+    pub parts: [Part; 2],
+    pub params: CheckParams,
+}
+
+fn with_params<P: Params>(sample: bool, overrides: &[(String, String)]) -> Result<P, String> {
+    let mut params = if sample { P::sample() } else { P::default() };
+    for (key, value) in overrides {
+        params.set(key, value)?;
+    }
+    info!("{params:?}");
+    Ok(params)
+}
+
+fn no_params(overrides: &[(String, String)]) -> Result<(), String> {
+    match overrides.first() {
+        Some((key, _)) => Err(format!("unknown parameter {key}, this day doesn't take any")),
+        None => Ok(()),
+    }
+}
+
+macro_rules! part {
+    ($year:ident::$module:ident::$part:ident) => {
+        |input, _, overrides| {
+            no_params(overrides)?;
+            crate::$year::$module::$part(input).answer()
+        }
+    };
+    ($year:ident::$module:ident::$part:ident with params) => {
+        |input, sample, overrides| {
+            let params: crate::$year::$module::Params = with_params(sample, overrides)?;
+            crate::$year::$module::$part(input, &params).answer()
+        }
+    };
+}
+
+macro_rules! check_params {
+    ($year:ident::$module:ident) => {
+        |_, overrides| no_params(overrides)
+    };
+    ($year:ident::$module:ident with params) => {
+        |sample, overrides| with_params::<crate::$year::$module::Params>(sample, overrides).map(|_| ())
+    };
+}
+
+macro_rules! day {
+    ($year:literal, $number:literal, $year_module:ident::$module:ident $($with:ident $params:ident)?) => {
+        Day {
+            year: $year,
+            number: $number,
+            parts: [
+                part!($year_module::$module::part_1_from_str $($with $params)?),
+                part!($year_module::$module::part_2_from_str $($with $params)?),
+            ],
+            params: check_params!($year_module::$module $($with $params)?),
+        }
+    };
+}
+
+// In order of year then day
+pub const DAYS: &[Day] = &[
+    day!(2022, 1,  y2022::day_1),
+    day!(2022, 2,  y2022::day_2 with params),
+    day!(2022, 3,  y2022::day_3 with params),
+    day!(2022, 4,  y2022::day_4),
+    day!(2022, 6,  y2022::day_6),
+    day!(2022, 7,  y2022::day_7 with params),
+    day!(2022, 12, y2022::day_12),
+    day!(2022, 13, y2022::day_13),
+    day!(2022, 14, y2022::day_14 with params),
+    day!(2022, 15, y2022::day_15 with params),
+    day!(2022, 16, y2022::day_16 with params),
+    day!(2022, 17, y2022::day_17 with params),
+    day!(2022, 18, y2022::day_18),
+    day!(2022, 20, y2022::day_20 with params),
+    day!(2022, 21, y2022::day_21),
+    day!(2022, 23, y2022::day_23 with params),
+    day!(2022, 24, y2022::day_24),
+];
+
+pub fn find(year: u32, number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.year == year && day.number == number)
+}
+
+pub fn latest_year() -> u32 {
+    DAYS.last().unwrap().year
+}
+
+pub fn latest_day(year: u32) -> Option<u32> {
+    DAYS.iter().rev().find(|day| day.year == year).map(|day| day.number)
+}
+
+pub fn input_path(year: u32, day: u32, sample: bool) -> String {
+    format!("input/{year}/{}/{day}.txt", if sample { "sample" } else { "real" })
+}
+
+// What the answers should be, one part per line, with ? for a part we don't know yet
+pub fn answers_path(year: u32, day: u32, sample: bool) -> String {
+    format!("input/{year}/{}/{day}.answers", if sample { "sample" } else { "real" })
+}
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// From now on panics are kept for solve_catching_panics to report instead of being printed
+pub fn capture_panics() {
+    panic::set_hook(Box::new(|info| {
+        LAST_PANIC.with(|last| *last.borrow_mut() = Some(info.to_string().replace('\n', " ")));
+    }));
+}
+
+// What to solve and with which input, shared by every command that runs solvers
+pub struct Options {
+    pub day: &'static Day,
+    pub sample: bool,
+    pub input: Option<String>,
+    pub overrides: Vec<(String, String)>,
+    pub stats: bool,
+    pub timeout: Option<Duration>,
+}
+
+pub const USAGE: &str = "[--year Y] [--day N] [--sample] [--input path] [--param key=value]... [--stats] [--timeout seconds]";
+
+// How long a part took and, when we counted, what it allocated
+pub struct Stats {
+    pub elapsed: Duration,
+    pub usage: Option<Usage>,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.2?}", self.elapsed)?;
+        if let Some(usage) = self.usage {
+            write!(f, ", {usage}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Options {
+    // --sample reads the worked example and switches to its parameters, --input reads any other file. Without --year
+    // and --day it's the latest year and the latest day in it. --stats counts allocations as well as timing each part,
+    // and --timeout gives each part that long before it gives up
+    pub fn from_args(args: &[String]) -> Result<Options, String> {
+        let mut year = None;
+        let mut day = None;
+        let mut sample = false;
+        let mut input = None;
+        let mut overrides = vec![];
+        let mut stats = false;
+        let mut timeout = None;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+            match arg.as_str() {
+                "--year" => year = Some(value()?.parse().map_err(|_| "--year needs a number")?),
+                "--day" => day = Some(value()?.parse().map_err(|_| "--day needs a number")?),
+                "--sample" => sample = true,
+                "--stats" => stats = true,
+                "--timeout" => {
+                    let seconds = value()?.parse().map_err(|_| "--timeout needs a number of seconds")?;
+                    timeout = Some(Duration::try_from_secs_f64(seconds).map_err(|e| format!("--timeout: {e}"))?);
+                }
+                "--input" => input = Some(value()?.clone()),
+                "--param" => {
+                    let (key, value) = value()?.split_once('=').ok_or("--param needs key=value")?;
+                    overrides.push((key.to_owned(), value.to_owned()));
+                }
+                _ => return Err(format!("unexpected {arg}, expected {USAGE}")),
+            }
+        }
+
+        let year = year.unwrap_or_else(latest_year);
+        let day = day.or_else(|| latest_day(year)).ok_or(format!("nothing from {year} is solved"))?;
+        let day = find(year, day).ok_or(format!("day {day} of {year} isn't solved"))?;
+        Ok(Options { day, sample, input, overrides, stats, timeout })
+    }
+
+    pub fn path(&self) -> String {
+        self.input.clone().unwrap_or_else(|| {
+            input_path(self.day.year, self.day.number, self.sample)
+        })
+    }
+
+    // Turns down overrides the day doesn't know or can't parse before anything is solved
+    pub fn check_params(&self) -> Result<(), String> {
+        (self.day.params)(self.sample, &self.overrides)
+    }
+
+    // part is 1 or 2
+    pub fn solve(&self, part: usize, input: &str) -> Result<String, String> {
+        cancel::with_timeout(self.timeout, || (self.day.parts[part - 1])(input, self.sample, &self.overrides))
+    }
+
+    pub fn solve_with_stats(&self, part: usize, input: &str) -> (Result<String, String>, Stats) {
+        let started = Instant::now();
+        let (answer, usage) = if self.stats {
+            let (answer, usage) = memory::measure(|| self.solve(part, input));
+            (answer, Some(usage))
+        } else {
+            (self.solve(part, input), None)
+        };
+        (answer, Stats { elapsed: started.elapsed(), usage })
+    }
+
+    // Most of the parsing is unwrap()s, so a malformed input shows up as a panic somewhere in the part
+    pub fn solve_catching_panics(&self, part: usize, input: &str) -> (Result<String, String>, Option<Stats>) {
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            let (answer, stats) = self.solve_with_stats(part, input);
+            (answer, Some(stats))
+        })).unwrap_or_else(|_| {
+            (Err(LAST_PANIC.with(|last| last.borrow_mut().take()).unwrap_or_else(|| "panicked".to_owned())), None)
+        })
+    }
+}
@@ -0,0 +1,110 @@
+use std::{fs, path::Path};
+
+// YEAR and DAY get replaced with the year and the day number. The parts answer with an error until they're written,
+// so that a new day only fails its own sample test rather than panicking wherever every day gets run
+const TEMPLATE: &str = r#"#[allow(dead_code)]
+pub fn part_1() -> Result<i64, String> {
+    part_1_from_str(&std::fs::read_to_string("input/YEAR/real/DAY.txt").unwrap())
+}
+
+pub fn part_1_from_str(_input: &str) -> Result<i64, String> {
+    Err("part 1 isn't solved yet".to_owned())
+}
+
+#[allow(dead_code)]
+pub fn part_2() -> Result<i64, String> {
+    part_2_from_str(&std::fs::read_to_string("input/YEAR/real/DAY.txt").unwrap())
+}
+
+pub fn part_2_from_str(_input: &str) -> Result<i64, String> {
+    Err("part 2 isn't solved yet".to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // input/YEAR/sample/DAY.answers has the worked example's answers, part 1 on the first line and part 2 on the second
+    #[test]
+    fn sample_answers() {
+        let input = std::fs::read_to_string("input/YEAR/sample/DAY.txt").unwrap();
+        let answers = std::fs::read_to_string("input/YEAR/sample/DAY.answers").unwrap();
+        let answers: Vec<&str> = answers.lines().collect();
+        assert_eq!(part_1_from_str(&input).map(|answer| answer.to_string()), Ok(answers[0].to_owned()));
+        assert_eq!(part_2_from_str(&input).map(|answer| answer.to_string()), Ok(answers[1].to_owned()));
+    }
+}
+"#;
+
+// ? is an answer we don't know yet
+const ANSWERS_PLACEHOLDER: &str = "?\n?\n";
+
+// Only ever creates files, so running it twice (or on a day that already exists) leaves everything alone
+fn create(path: &str, contents: &str, done: &mut Vec<String>) -> Result<(), String> {
+    if Path::new(path).exists() {
+        done.push(format!("kept {path}"));
+        return Ok(());
+    }
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", parent.display()))?;
+    }
+    fs::write(path, contents).map_err(|e| format!("{path}: {e}"))?;
+    done.push(format!("created {path}"));
+    Ok(())
+}
+
+// Put `line` among the run of lines `is_entry` picks out, keeping them sorted by `key`, unless it's there already
+fn register(path: &str, line: &str, key: u32, is_entry: impl Fn(&str) -> Option<u32>, done: &mut Vec<String>) -> Result<(), String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    let mut lines: Vec<&str> = contents.lines().collect();
+    if lines.contains(&line) {
+        done.push(format!("kept {path}"));
+        return Ok(());
+    }
+    let entries: Vec<(usize, u32)> = lines.iter().enumerate()
+        .filter_map(|(i, line)| Some((i, is_entry(line)?)))
+        .collect();
+    let Some(&(last, _)) = entries.last() else {
+        return Err(format!("{path}: couldn't find where to add {line}"));
+    };
+    let at = entries.iter().find(|&&(_, n)| n > key).map(|&(i, _)| i).unwrap_or(last + 1);
+    lines.insert(at, line);
+    fs::write(path, lines.join("\n") + "\n").map_err(|e| format!("{path}: {e}"))?;
+    done.push(format!("added {} to {path}", line.trim()));
+    Ok(())
+}
+
+// Everything a new day needs, starting the year's module too if it's the first day of a new year. Reports what it did
+pub fn new_day(year: u32, day: u32) -> Result<Vec<String>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("there's no day {day} in the calendar"));
+    }
+    if !(2015..=9999).contains(&year) {
+        return Err(format!("there was no advent of code in {year}"));
+    }
+    let mut done = vec![];
+    let template = TEMPLATE.replace("YEAR", &year.to_string()).replace("DAY", &day.to_string());
+    create(&format!("src/y{year}/day_{day}.rs"), &template, &mut done)?;
+    create(&format!("input/{year}/sample/{day}.txt"), "", &mut done)?;
+    create(&format!("input/{year}/sample/{day}.answers"), ANSWERS_PLACEHOLDER, &mut done)?;
+
+    let year_module = format!("src/y{year}/mod.rs");
+    if Path::new(&year_module).exists() {
+        register(&year_module, &format!("pub mod day_{day};"), day, |line| {
+            line.strip_prefix("pub mod day_")?.strip_suffix(';')?.parse().ok()
+        }, &mut done)?;
+    } else {
+        create(&year_module, &format!("pub mod day_{day};\n"), &mut done)?;
+    }
+    register("src/main.rs", &format!("mod y{year};"), year, |line| {
+        line.strip_prefix("mod y")?.strip_suffix(';')?.parse().ok()
+    }, &mut done)?;
+    let padding = if day < 10 { "  " } else { " " };
+    register("src/runner.rs", &format!("    day!({year}, {day},{padding}y{year}::day_{day}),"), year * 100 + day, |line| {
+        let mut fields = line.trim().strip_prefix("day!(")?.split(',');
+        let year: u32 = fields.next()?.trim().parse().ok()?;
+        let day: u32 = fields.next()?.trim().parse().ok()?;
+        Some(year * 100 + day)
+    }, &mut done)?;
+    Ok(done)
+}
@@ -0,0 +1,278 @@
+use std::{io::{BufRead, BufReader, Read, Write}, net::{TcpListener, TcpStream}, thread, time::Duration};
+
+use crate::runner::{self, Options, DAYS};
+
+// The most we'll read of a request body, which is plenty for any puzzle input
+const MAX_BODY: usize = 64 << 20;
+// The request line and the headers are only ever a few short lines, so anything past these is not a client of ours
+const MAX_LINE: usize = 8 << 10;
+const MAX_HEADERS: usize = 100;
+// How long a client can leave us waiting for the rest of its request
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    body: String,
+}
+
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn error(status: u16, kind: &str, message: &str) -> Response {
+        Response { status, body: format!(r#"{{"error":{{"kind":{},"message":{}}}}}"#, json_string(kind), json_string(message)) }
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        431 => "Request Header Fields Too Large",
+        _   => "Internal Server Error",
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            c if (c as u32) < 0x20 => out += &format!("\\u{:04x}", c as u32),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn read_line(reader: &mut impl BufRead) -> Result<String, Response> {
+    let mut line = String::new();
+    reader.take(MAX_LINE as u64 + 1).read_line(&mut line)
+        .map_err(|e| Response::error(400, "bad_request", &e.to_string()))?;
+    if line.len() > MAX_LINE {
+        return Err(Response::error(431, "too_large", &format!("request lines are limited to {MAX_LINE} bytes")));
+    }
+    Ok(line)
+}
+
+// Just enough HTTP/1.1 for a client on the same machine: a request line, headers, and a body of Content-Length bytes
+fn read_request(reader: &mut impl BufRead) -> Result<Request, Response> {
+    let bad = |message: &str| Response::error(400, "bad_request", message);
+    let line = read_line(reader)?;
+    let mut fields = line.split_whitespace();
+    let (Some(method), Some(target)) = (fields.next(), fields.next()) else {
+        return Err(bad("expected a request line"));
+    };
+
+    let mut content_length = 0;
+    for headers in 0.. {
+        let header = read_line(reader)?;
+        if header.trim().is_empty() {
+            break;
+        }
+        if headers == MAX_HEADERS {
+            return Err(Response::error(431, "too_large", &format!("requests are limited to {MAX_HEADERS} headers")));
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().map_err(|_| bad("bad Content-Length"))?;
+            }
+        }
+    }
+    if content_length > MAX_BODY {
+        return Err(Response::error(413, "too_large", &format!("inputs are limited to {MAX_BODY} bytes")));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).map_err(|e| bad(&e.to_string()))?;
+    let body = String::from_utf8(body).map_err(|_| bad("the input isn't UTF-8"))?;
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, "true"));
+            (key.to_owned(), value.to_owned())
+        }).collect();
+    Ok(Request { method: method.to_owned(), path: path.to_owned(), query, body })
+}
+
+fn days() -> Response {
+    let days: Vec<String> = DAYS.iter().map(|day| format!(r#"{{"year":{},"day":{}}}"#, day.year, day.number)).collect();
+    Response { status: 200, body: format!(r#"{{"days":[{}]}}"#, days.join(",")) }
+}
+
+// `year` and `sample` pick the day and its parameters, anything else in the query overrides a parameter
+fn solve(request: &Request, day: &str, part: &str, timeout: Option<Duration>) -> Response {
+    let mut year = runner::latest_year();
+    let mut sample = false;
+    let mut overrides = vec![];
+    for (key, value) in &request.query {
+        match key.as_str() {
+            "year" => match value.parse() {
+                Ok(y) => year = y,
+                Err(_) => return Response::error(400, "bad_request", &format!("not a year: {value}")),
+            },
+            "sample" => sample = value != "false",
+            _ => overrides.push((key.clone(), value.clone())),
+        }
+    }
+    let Some(day) = day.parse().ok().and_then(|number| runner::find(year, number)) else {
+        return Response::error(404, "not_found", &format!("day {day} of {year} isn't solved"));
+    };
+    let part = match part {
+        "1" => 1,
+        "2" => 2,
+        _ => return Response::error(404, "not_found", &format!("there's no part {part}")),
+    };
+
+    let options = Options { day, sample, input: None, overrides, stats: false, timeout };
+    if let Err(message) = options.check_params() {
+        return Response::error(400, "bad_request", &message);
+    }
+    match options.solve_catching_panics(part, &request.body) {
+        (Ok(answer), Some(stats)) => Response {
+            status: 200,
+            body: format!(
+                r#"{{"year":{},"day":{},"part":{part},"answer":{},"elapsed_ms":{:.3}}}"#,
+                day.year, day.number, json_string(&answer), stats.elapsed.as_secs_f64() * 1000.0,
+            ),
+        },
+        // the solver gave up
+        (Err(message), Some(_)) => Response::error(422, "no_answer", &message),
+        // the parsers unwrap() their way through the input, so a panic means it wasn't what the day expects
+        (Err(message), None) => Response::error(400, "parse", &message),
+        (Ok(_), None) => unreachable!(),
+    }
+}
+
+fn route(request: &Request, timeout: Option<Duration>) -> Response {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["days"]) => days(),
+        ("POST", ["solve", day, part]) => solve(request, day, part, timeout),
+        (_, ["days"]) | (_, ["solve", _, _]) => Response::error(405, "method_not_allowed", &format!("{} isn't allowed here", request.method)),
+        _ => Response::error(404, "not_found", &format!("nothing at {}", request.path)),
+    }
+}
+
+fn handle(stream: TcpStream, timeout: Option<Duration>) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let mut reader = BufReader::new(&stream);
+    let response = read_request(&mut reader).map(|request| route(&request, timeout)).unwrap_or_else(|e| e);
+    let mut stream = &stream;
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status, reason(response.status), response.body.len(), response.body,
+    );
+    let _ = stream.flush();
+}
+
+// Answer requests on `listener` until the process is stopped, each connection on its own thread:
+//   GET /days lists what's solved
+//   POST /solve/{day}/{part}[?year=Y&sample&key=value...] solves the body
+// A parse error only says which panic it was once the caller has run runner::capture_panics, which main does, and the
+// tests don't so that the hook stays the default for every other test
+pub fn serve(listener: TcpListener, timeout: Option<Duration>) -> ! {
+    for stream in listener.incoming().flatten() {
+        thread::spawn(move || handle(stream, timeout));
+    }
+    unreachable!();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::SocketAddr, sync::OnceLock};
+
+    use super::*;
+
+    // One server for all the tests, on whatever port is free
+    fn server() -> SocketAddr {
+        static ADDRESS: OnceLock<SocketAddr> = OnceLock::new();
+        *ADDRESS.get_or_init(|| {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let address = listener.local_addr().unwrap();
+            thread::spawn(move || serve(listener, Some(Duration::from_secs(10))));
+            address
+        })
+    }
+
+    fn request(method: &str, target: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(server()).unwrap();
+        write!(stream, "{method} {target} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{body}", body.len()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        (head.split(' ').nth(1).unwrap().parse().unwrap(), body.to_owned())
+    }
+
+    fn sample(day: u32) -> String {
+        std::fs::read_to_string(runner::input_path(2022, day, true)).unwrap()
+    }
+
+    #[test]
+    fn lists_the_days() {
+        let (status, body) = request("GET", "/days", "");
+        assert_eq!(status, 200);
+        assert!(body.starts_with(r#"{"days":[{"year":"#) && body.contains(r#"{"year":2022,"day":1}"#), "{body}");
+    }
+
+    #[test]
+    fn solves_a_posted_input() {
+        let (status, body) = request("POST", "/solve/1/2?year=2022", &sample(1));
+        assert_eq!(status, 200);
+        assert!(body.starts_with(r#"{"year":2022,"day":1,"part":2,"answer":"45000","elapsed_ms":"#), "{body}");
+    }
+
+    #[test]
+    fn applies_sample_and_parameters() {
+        let (_, body) = request("POST", "/solve/15/1?year=2022&sample", &sample(15));
+        assert!(body.contains(r#""answer":"26""#), "{body}");
+        let (_, body) = request("POST", "/solve/15/1?year=2022&sample&target_y=11", &sample(15));
+        assert!(body.contains(r#""answer":"28""#), "{body}");
+        let (status, body) = request("POST", "/solve/15/1?year=2022&sample&target_z=11", &sample(15));
+        assert_eq!(status, 400);
+        assert!(body.starts_with(r#"{"error":{"kind":"bad_request","message":"unknown parameter target_z"#), "{body}");
+    }
+
+    #[test]
+    fn reports_inputs_that_dont_parse() {
+        let (status, body) = request("POST", "/solve/4/1?year=2022", "1-2,3-\"four\"\n");
+        assert_eq!(status, 400);
+        assert!(body.starts_with(r#"{"error":{"kind":"parse","message":"#), "{body}");
+    }
+
+    #[test]
+    fn reports_bad_requests() {
+        // new-day only scaffolds days 1 to 25, so there will never be a day 26
+        assert_eq!(request("POST", "/solve/26/1?year=2022", "").0, 404);
+        assert_eq!(request("POST", "/solve/1/3?year=2022", "").0, 404);
+        assert_eq!(request("GET", "/solve/1/1?year=2022", "").0, 405);
+        assert_eq!(request("GET", "/nowhere", "").0, 404);
+        let (status, body) = request("POST", "/solve/1/1?year=twenty", "");
+        assert_eq!(status, 400);
+        assert_eq!(body, r#"{"error":{"kind":"bad_request","message":"not a year: twenty"}}"#);
+    }
+
+    #[test]
+    fn turns_down_oversized_headers() {
+        let status = |request: String| read_request(&mut request.as_bytes()).err().map(|response| response.status);
+        assert_eq!(status(format!("GET /days HTTP/1.1\r\n{}\r\n", "X: y\r\n".repeat(MAX_HEADERS))), None);
+        assert_eq!(status(format!("GET /days HTTP/1.1\r\n{}\r\n", "X: y\r\n".repeat(MAX_HEADERS + 1))), Some(431));
+        assert_eq!(status(format!("GET /days HTTP/1.1\r\nX: {}\r\n\r\n", "y".repeat(MAX_LINE))), Some(431));
+        assert_eq!(status(format!("GET /{} HTTP/1.1\r\n\r\n", "y".repeat(MAX_LINE))), Some(431));
+    }
+}
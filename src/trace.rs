@@ -0,0 +1,102 @@
+use std::{env, fmt, sync::{RwLock, atomic::{AtomicU8, Ordering}}};
+
+// Events go to stderr so they never get mixed up with the answers
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level { Info = 1, Debug = 2, Trace = 3 }
+
+impl Level {
+    fn from_str(s: &str) -> Option<u8> {
+        match s {
+            "off"   => Some(0),
+            "info"  => Some(Level::Info as u8),
+            "debug" => Some(Level::Debug as u8),
+            "trace" => Some(Level::Trace as u8),
+            _       => None,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Level::Info  => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        })
+    }
+}
+
+pub const ENV_VAR: &str = "ADVENT_TRACE";
+
+// The most verbose level any target has switched on, so disabled events cost one atomic load
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+// Everything not otherwise named, then (target, level) overrides such as y2022::day_17=trace
+static FILTER: RwLock<(u8, Vec<(String, u8)>)> = RwLock::new((0, Vec::new()));
+
+// `verbosity` is how many -v flags were given. ADVENT_TRACE takes comma separated directives, each either a level
+// for everything or target=level for one day, e.g. ADVENT_TRACE=info,y2022::day_24=trace. A target names a module,
+// a whole year (y2022) or, on its own, a day in any year (day_24)
+pub fn init(verbosity: u8) -> Result<(), String> {
+    let mut default = verbosity.min(Level::Trace as u8);
+    let mut targets = vec![];
+    for directive in env::var(ENV_VAR).unwrap_or_default().split(',').map(str::trim).filter(|d| !d.is_empty()) {
+        let bad = || format!("{ENV_VAR}: can't make sense of {directive}");
+        match directive.split_once('=') {
+            Some((target, level)) => targets.push((target.to_owned(), Level::from_str(level).ok_or_else(bad)?)),
+            None => default = default.max(Level::from_str(directive).ok_or_else(bad)?),
+        }
+    }
+
+    let max = targets.iter().map(|&(_, level)| level).fold(default, u8::max);
+    *FILTER.write().unwrap() = (default, targets);
+    MAX_LEVEL.store(max, Ordering::Relaxed);
+    Ok(())
+}
+
+// Targets are module names with the crate name taken off, so y2022::day_17 rather than advent_2022::y2022::day_17,
+// and main for the crate root
+fn target_of(module_path: &str) -> &str {
+    module_path.split_once("::").map(|(_, rest)| rest).unwrap_or("main")
+}
+
+pub fn enabled(level: Level, module_path: &str) -> bool {
+    if level as u8 > MAX_LEVEL.load(Ordering::Relaxed) {
+        return false;
+    }
+    let target = target_of(module_path);
+    let filter = FILTER.read().unwrap();
+    let allowed = filter.1.iter()
+        .find(|(name, _)| {
+            target == name || target.starts_with(&format!("{name}::")) || target.ends_with(&format!("::{name}"))
+        })
+        .map(|&(_, level)| level)
+        .unwrap_or(filter.0);
+    level as u8 <= allowed
+}
+
+pub fn emit(level: Level, module_path: &str, args: fmt::Arguments) {
+    eprintln!("{level:>5} {}: {args}", target_of(module_path));
+}
+
+// The arguments are only evaluated when the event is going to be shown
+macro_rules! event {
+    ($level:expr, $($arg:tt)*) => {
+        if crate::trace::enabled($level, module_path!()) {
+            crate::trace::emit($level, module_path!(), format_args!($($arg)*));
+        }
+    };
+}
+
+macro_rules! info {
+    ($($arg:tt)*) => { crate::trace::event!(crate::trace::Level::Info, $($arg)*) };
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => { crate::trace::event!(crate::trace::Level::Debug, $($arg)*) };
+}
+
+macro_rules! trace {
+    ($($arg:tt)*) => { crate::trace::event!(crate::trace::Level::Trace, $($arg)*) };
+}
+
+pub(crate) use {debug, event, info, trace};
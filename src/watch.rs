@@ -0,0 +1,45 @@
+use std::{fs, thread, time::{Duration, SystemTime}};
+
+use crate::runner::{self, Options};
+
+const POLL_EVERY: Duration = Duration::from_millis(250);
+
+fn describe(answer: &Result<String, String>, previous: Option<&Result<String, String>>) -> String {
+    match (answer, previous) {
+        (Err(e), _) => format!("error: {e}"),
+        (Ok(answer), Some(Ok(previous))) if answer == previous => format!("{answer} (unchanged)"),
+        (Ok(answer), Some(Ok(previous))) => format!("{answer} (was {previous})"),
+        (Ok(answer), _) => answer.clone(),
+    }
+}
+
+// Poll the input's modification time and size, re-solving both parts whenever either moves. Never returns
+pub fn watch(options: &Options) -> ! {
+    // panics get reported next to the part they broke
+    runner::capture_panics();
+
+    let path = options.path();
+    let mut seen: Option<Option<(SystemTime, u64)>> = None;
+    let mut previous: [Option<Result<String, String>>; 2] = [None, None];
+    println!("watching {path} for day {}", options.day.number);
+
+    loop {
+        let stamp = fs::metadata(&path).and_then(|m| Ok((m.modified()?, m.len()))).ok();
+        if seen != Some(stamp) {
+            seen = Some(stamp);
+            match fs::read_to_string(&path) {
+                Err(e) => println!("can't read {path}: {e}"),
+                Ok(input) => {
+                    println!("-- {path} changed");
+                    for (part, previous) in (1..=2).zip(previous.iter_mut()) {
+                        let (answer, stats) = options.solve_catching_panics(part, &input);
+                        let stats = stats.filter(|_| options.stats).map(|s| format!(" [{s}]")).unwrap_or_default();
+                        println!("part {part}: {}{stats}", describe(&answer, previous.as_ref()));
+                        *previous = Some(answer);
+                    }
+                }
+            }
+        }
+        thread::sleep(POLL_EVERY);
+    }
+}
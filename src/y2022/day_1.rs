@@ -0,0 +1,437 @@
+use std::{cmp::{Ordering, Reverse}, collections::BinaryHeap, fmt, fs::File, io::{self, BufRead, BufReader}, mem, str};
+
+use crate::{cancel::Unsolved, generate::Rng};
+
+// One elf's pack: where the elf comes in the list (counting from 0), how many snacks and how many calories altogether
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Elf {
+    pub index: usize,
+    pub items: usize,
+    pub total: u64,
+}
+
+// Why the elves couldn't be added up: the reader failed, a line wasn't a number, or the elves gave up one of the usual
+// reasons
+#[derive(Debug)]
+pub enum Error {
+    Read(io::Error),
+    // lines count from 1
+    Parse { line: usize, text: String },
+    Unsolved(Unsolved),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Read(e)
+    }
+}
+
+impl From<Unsolved> for Error {
+    fn from(e: Unsolved) -> Error {
+        Error::Unsolved(e)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Read(e) => e.fmt(f),
+            Error::Parse { line, text } => write!(f, "line {line}: {text:?} isn't a number of calories"),
+            Error::Unsolved(e) => e.fmt(f),
+        }
+    }
+}
+
+// io::Error can't be compared, so read errors are the same when they're the same kind
+impl PartialEq for Error {
+    fn eq(&self, other: &Error) -> bool {
+        match (self, other) {
+            (Error::Read(a), Error::Read(b)) => a.kind() == b.kind(),
+            (Error::Parse { line: a, text: a_text }, Error::Parse { line: b, text: b_text }) => (a, a_text) == (b, b_text),
+            (Error::Unsolved(a), Error::Unsolved(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+// Reads elves one at a time, so only the elf being added up is ever in memory. An elf carrying more than a u64 can
+// count comes out as Overflow. A reader that fails, or that isn't UTF-8, ends the elves with a Read error, and a line
+// that isn't a number ends them with a Parse error
+pub struct Elves<R> {
+    reader: R,
+    line: Vec<u8>,
+    started: bool,
+    // the last line ended in \r, so a \n straight after belongs to it
+    after_cr: bool,
+    failed: bool,
+    lines_read: usize,
+    next_index: usize,
+}
+
+impl<R: BufRead> Elves<R> {
+    // The next line, ended by \n, \r\n or a lone \r, with nothing trailing. None once there's nothing left
+    fn read_line(&mut self) -> io::Result<Option<&str>> {
+        self.line.clear();
+        if !mem::replace(&mut self.started, true) && self.reader.fill_buf()?.starts_with("\u{feff}".as_bytes()) {
+            self.reader.consume("\u{feff}".len());
+        }
+        loop {
+            let buf = self.reader.fill_buf()?;
+            if buf.is_empty() {
+                if self.line.is_empty() {
+                    return Ok(None);
+                }
+                break;
+            }
+            if mem::take(&mut self.after_cr) && buf[0] == b'\n' {
+                self.reader.consume(1);
+                continue;
+            }
+            if let Some(end) = buf.iter().position(|&b| b == b'\n' || b == b'\r') {
+                self.line.extend_from_slice(&buf[..end]);
+                self.after_cr = buf[end] == b'\r';
+                self.reader.consume(end + 1);
+                break;
+            }
+            let len = buf.len();
+            self.line.extend_from_slice(buf);
+            self.reader.consume(len);
+        }
+        self.lines_read += 1;
+        let line = str::from_utf8(&self.line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(Some(line.trim_end()))
+    }
+}
+
+impl<R: BufRead> Iterator for Elves<R> {
+    type Item = Result<Elf, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let mut elf: Option<Elf> = None;
+        let mut overflowed = false;
+        loop {
+            let line = match self.read_line() {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(e) => {
+                    self.failed = true;
+                    return Some(Err(e.into()));
+                }
+            };
+            if line.is_empty() {
+                // any number of blank lines between elves, or at either end
+                if elf.is_some() { break; } else { continue; }
+            }
+            let Ok(calories) = line.parse::<u64>() else {
+                let text = line.to_owned();
+                self.failed = true;
+                let error = Error::Parse { line: self.lines_read, text };
+                return Some(Err(error));
+            };
+            let elf = elf.get_or_insert(Elf { index: self.next_index, items: 0, total: 0 });
+            elf.items += 1;
+            match elf.total.checked_add(calories) {
+                Some(total) => elf.total = total,
+                None => overflowed = true,
+            }
+        }
+        let elf = elf?;
+        self.next_index += 1;
+        Some(if overflowed { Err(Unsolved::Overflow.into()) } else { Ok(elf) })
+    }
+}
+
+pub fn elves<R: BufRead>(reader: R) -> Elves<R> {
+    Elves { reader, line: vec![], started: false, after_cr: false, failed: false, lines_read: 0, next_index: 0 }
+}
+
+// More calories ranks higher, and between elves carrying the same the one earlier in the list does
+#[derive(PartialEq, Eq)]
+struct ByCalories(Elf);
+
+impl Ord for ByCalories {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total.cmp(&other.0.total).then(other.0.index.cmp(&self.0.index))
+    }
+}
+
+impl PartialOrd for ByCalories {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// The best `k` elves, best first. Ties go to whoever came first, and `tied_with_last` counts the elves left out who
+// carry as much as the last one in
+#[derive(Debug, PartialEq, Eq)]
+pub struct Ranking {
+    pub top: Vec<Elf>,
+    pub tied_with_last: usize,
+}
+
+// Keeps only the `k` best elves seen so far in a min-heap, so memory doesn't grow with the inventory
+pub fn top_elves(reader: impl BufRead, k: usize) -> Result<Ranking, Error> {
+    let mut heap: BinaryHeap<Reverse<ByCalories>> = BinaryHeap::with_capacity(k + 1);
+    let mut tied_with_last = 0;
+    let mut cutoff = None;
+    for elf in elves(reader) {
+        let elf = elf?;
+        if k == 0 {
+            continue;
+        }
+        heap.push(Reverse(ByCalories(elf)));
+        if heap.len() <= k {
+            continue;
+        }
+        let Reverse(ByCalories(left_out)) = heap.pop().unwrap();
+        let last = heap.peek().unwrap().0.0.total;
+        if cutoff != Some(last) {
+            cutoff = Some(last);
+            tied_with_last = 0;
+        }
+        if left_out.total == last {
+            tied_with_last += 1;
+        }
+    }
+    let top = heap.into_sorted_vec().into_iter().map(|Reverse(ByCalories(elf))| elf).collect();
+    Ok(Ranking { top, tied_with_last })
+}
+
+// What the best `k` elves carry between them. NoSolution if there aren't that many elves
+fn top_total(reader: impl BufRead, k: usize) -> Result<u64, Error> {
+    let ranking = top_elves(reader, k)?;
+    if ranking.top.len() < k {
+        return Err(Unsolved::NoSolution.into());
+    }
+    Ok(ranking.top.iter().map(|elf| elf.total).try_fold(0, u64::checked_add).ok_or(Unsolved::Overflow)?)
+}
+
+pub fn real_input() -> BufReader<File> {
+    BufReader::new(File::open("input/2022/real/1.txt").unwrap())
+}
+
+#[allow(dead_code)]
+pub fn part_1() -> Result<u64, Error> {
+    top_total(real_input(), 1)
+}
+
+pub fn part_1_from_str(input: &str) -> Result<u64, Error> {
+    top_total(input.as_bytes(), 1)
+}
+
+#[allow(dead_code)]
+pub fn part_2() -> Result<u64, Error> {
+    top_total(real_input(), 3)
+}
+
+pub fn part_2_from_str(input: &str) -> Result<u64, Error> {
+    top_total(input.as_bytes(), 3)
+}
+
+const PERCENTILES: [u32; 6] = [10, 25, 50, 75, 90, 99];
+const HISTOGRAM_BUCKETS: u64 = 10;
+const HISTOGRAM_WIDTH: usize = 40;
+const MOST_ITEMS: usize = 3;
+
+// Everything about who's carrying what
+#[derive(Debug, PartialEq)]
+pub struct Inventory {
+    pub elves: usize,
+    pub mean: f64,
+    pub median: f64,
+    // (percentile, total), by nearest rank
+    pub percentiles: Vec<(u32, u64)>,
+    // (lowest total, highest total, elves), in equal-width buckets from the lightest pack to the heaviest
+    pub histogram: Vec<(u64, u64, usize)>,
+    // most snacks first, ties to the earlier elf
+    pub most_items: Vec<Elf>,
+    // beyond one and a half interquartile ranges from the middle half (Tukey's fences)
+    pub outliers: Vec<Elf>,
+}
+
+// The total at least `percentile`% of elves carry no more than
+fn nearest_rank(sorted: &[u64], percentile: u32) -> u64 {
+    let rank = (percentile as usize * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+// NoSolution if there are no elves to look at
+pub fn inventory(reader: impl BufRead) -> Result<Inventory, Error> {
+    let elves: Vec<Elf> = elves(reader).collect::<Result<_, _>>()?;
+    if elves.is_empty() {
+        return Err(Unsolved::NoSolution.into());
+    }
+    let mut sorted: Vec<u64> = elves.iter().map(|elf| elf.total).collect();
+    sorted.sort_unstable();
+    let count = sorted.len();
+
+    let mean = (sorted.iter().map(|&total| total as u128).sum::<u128>() as f64) / count as f64;
+    let median = if count % 2 == 1 {
+        sorted[count / 2] as f64
+    } else {
+        (sorted[count / 2 - 1] as f64 + sorted[count / 2] as f64) / 2.0
+    };
+    let percentiles = PERCENTILES.iter().map(|&p| (p, nearest_rank(&sorted, p))).collect();
+
+    let (lightest, heaviest) = (sorted[0], sorted[count - 1]);
+    let bucket_width = ((heaviest - lightest) / HISTOGRAM_BUCKETS).saturating_add(1);
+    let buckets = (heaviest - lightest) / bucket_width + 1;
+    let histogram = (0..buckets)
+        .map(|bucket| {
+            let low = lightest + bucket * bucket_width;
+            let high = low.saturating_add(bucket_width - 1).min(heaviest);
+            (low, high, sorted.iter().filter(|&&total| (low..=high).contains(&total)).count())
+        }).collect();
+
+    let mut most_items = elves.clone();
+    most_items.sort_by(|a, b| b.items.cmp(&a.items).then(a.index.cmp(&b.index)));
+    most_items.truncate(MOST_ITEMS);
+
+    let (q1, q3) = (nearest_rank(&sorted, 25) as f64, nearest_rank(&sorted, 75) as f64);
+    let (low_fence, high_fence) = (q1 - 1.5 * (q3 - q1), q3 + 1.5 * (q3 - q1));
+    let outliers = elves.into_iter()
+        .filter(|elf| (elf.total as f64) < low_fence || (elf.total as f64) > high_fence)
+        .collect();
+
+    Ok(Inventory { elves: count, mean, median, percentiles, histogram, most_items, outliers })
+}
+
+impl fmt::Display for Inventory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} elves, carrying {:.1} calories on average and {} in the middle", self.elves, self.mean, self.median)?;
+        let percentiles: Vec<String> = self.percentiles.iter().map(|(p, total)| format!("p{p} {total}")).collect();
+        writeln!(f, "percentiles: {}", percentiles.join(", "))?;
+
+        let most = self.histogram.iter().map(|&(_, _, elves)| elves).max().unwrap_or(0).max(1);
+        let digits = self.histogram.last().map(|&(_, high, _)| high.to_string().len()).unwrap_or(1);
+        for &(low, high, elves) in &self.histogram {
+            // rounded up, so a bucket with anyone in it always shows
+            let bar = "#".repeat((elves * HISTOGRAM_WIDTH).div_ceil(most));
+            let bar = if bar.is_empty() { elves.to_string() } else { format!("{bar} {elves}") };
+            writeln!(f, "{low:>digits$} - {high:>digits$} | {bar}")?;
+        }
+
+        let describe = |elves: &[Elf]| elves.iter()
+            .map(|elf| format!("elf {} ({} items, {} calories)", elf.index, elf.items, elf.total))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(f, "most items: {}", describe(&self.most_items))?;
+        if self.outliers.is_empty() {
+            writeln!(f, "outliers: none")
+        } else {
+            writeln!(f, "outliers: {}", describe(&self.outliers))
+        }
+    }
+}
+
+// `size` elves, each carrying a handful of snacks
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let elves: Vec<String> = (0..size)
+        .map(|_| {
+            let items = rng.range(1..=15);
+            (0..items).map(|_| rng.range(1000..=60000).to_string()).collect::<Vec<_>>().join("\n")
+        }).collect();
+    elves.join("\n\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::differential;
+
+    // Every elf's total, sorted best first
+    fn reference(input: &str, k: usize) -> Ranking {
+        let mut totals: Vec<Elf> = elves(input.as_bytes()).map(Result::unwrap).collect();
+        totals.sort_by(|a, b| b.total.cmp(&a.total).then(a.index.cmp(&b.index)));
+        let left_out = totals.split_off(k.min(totals.len()));
+        let tied_with_last = totals.last().map(|last| left_out.iter().filter(|elf| elf.total == last.total).count()).unwrap_or(0);
+        Ranking { top: totals, tied_with_last }
+    }
+
+    #[test]
+    fn top_elves_matches_sorting_everyone() {
+        for k in [0, 1, 3, 10] {
+            // few distinct totals, so plenty of ties
+            let generate = |rng: &mut Rng, size: usize| {
+                (0..size).map(|_| (0..rng.range(1..=3)).map(|_| format!("{}\n", rng.range(1..=3))).collect::<String>() + "\n").collect::<String>()
+            };
+            differential(generate, 0..=30, 100, |input| top_elves(input.as_bytes(), k), |input| Some(Ok(reference(input, k))));
+        }
+    }
+
+    #[test]
+    fn ties_go_to_the_first_elf() {
+        let input = "5\n\n9\n\n2\n3\n\n\n\n9\n\n1\n\n5\n";
+        let elf = |index, items, total| Elf { index, items, total };
+        assert_eq!(top_elves(input.as_bytes(), 2), Ok(Ranking { top: vec![elf(1, 1, 9), elf(3, 1, 9)], tied_with_last: 0 }));
+        assert_eq!(
+            top_elves(input.as_bytes(), 3),
+            Ok(Ranking { top: vec![elf(1, 1, 9), elf(3, 1, 9), elf(0, 1, 5)], tied_with_last: 2 }),
+        );
+        assert_eq!(top_elves(input.as_bytes(), 0), Ok(Ranking { top: vec![], tied_with_last: 0 }));
+        assert_eq!(top_elves(input.as_bytes(), 10).map(|ranking| ranking.top.len()), Ok(6));
+    }
+
+    #[test]
+    fn sample_inventory() {
+        let inventory = inventory(std::fs::read_to_string("input/2022/sample/1.txt").unwrap().as_bytes()).unwrap();
+        assert_eq!(inventory.elves, 5);
+        assert_eq!((inventory.mean, inventory.median), (11000.0, 10000.0));
+        assert_eq!(inventory.percentiles[1..4], [(25, 6000), (50, 10000), (75, 11000)]);
+        assert_eq!(inventory.histogram.iter().map(|&(_, _, elves)| elves).sum::<usize>(), 5);
+        assert_eq!(inventory.histogram.first().map(|&(low, _, elves)| (low, elves)), Some((4000, 2)));
+        assert_eq!(inventory.histogram.last().map(|&(_, high, _)| high), Some(24000));
+        assert_eq!(inventory.most_items.iter().map(|elf| elf.index).collect::<Vec<_>>(), [0, 3, 2]);
+        // 24000 is past 11000 + 1.5 * (11000 - 6000)
+        assert_eq!(inventory.outliers.iter().map(|elf| elf.index).collect::<Vec<_>>(), [3]);
+    }
+
+    #[test]
+    fn reads_windows_inputs() {
+        assert_eq!(part_2_from_str("\u{feff}1\r\n2\r\n\r\n3\r\n\r\n4\r\n"), Ok(10));
+        assert_eq!(part_2_from_str("\u{feff}1\r2\r\r3\r\n\r4"), Ok(10));
+    }
+
+    struct Unplugged;
+
+    impl io::Read for Unplugged {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("unplugged"))
+        }
+    }
+
+    #[test]
+    fn read_errors_end_the_elves() {
+        let mut elves = elves(BufReader::new(io::Read::chain("1\n\n2\n".as_bytes(), Unplugged)));
+        assert_eq!(elves.next(), Some(Ok(Elf { index: 0, items: 1, total: 1 })));
+        assert_eq!(elves.next(), Some(Err(Error::Read(io::Error::other("unplugged")))));
+        assert_eq!(elves.next(), None);
+        assert_eq!(top_elves(&b"1\n\n\xff\n"[..], 1), Err(Error::Read(io::ErrorKind::InvalidData.into())));
+    }
+
+    #[test]
+    fn parse_errors_end_the_elves() {
+        let mut elves = elves("\u{feff}1\r\n\r\n2\r\nthree\r\n4\r\n".as_bytes());
+        assert_eq!(elves.next(), Some(Ok(Elf { index: 0, items: 1, total: 1 })));
+        assert_eq!(elves.next(), Some(Err(Error::Parse { line: 4, text: "three".to_owned() })));
+        assert_eq!(elves.next(), None);
+        assert_eq!(part_1_from_str("1\n-2\n").map_err(|e| e.to_string()), Err(r#"line 2: "-2" isn't a number of calories"#.to_owned()));
+    }
+
+    #[test]
+    fn totals_past_i32_are_counted() {
+        let input = "2000000000\n2000000000\n\n1\n\n3000000000\n";
+        assert_eq!(part_1_from_str(input), Ok(4_000_000_000));
+        assert_eq!(part_2_from_str(input), Ok(7_000_000_001));
+    }
+
+    #[test]
+    fn totals_past_u64_overflow() {
+        let big = u64::MAX / 2 + 1;
+        assert_eq!(part_1_from_str(&format!("{big}\n{big}\n")), Err(Error::Unsolved(Unsolved::Overflow)));
+        assert_eq!(part_2_from_str(&format!("{big}\n\n{big}\n\n1\n")), Err(Error::Unsolved(Unsolved::Overflow)));
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+
+use crate::{generate::Rng, input::Input, render::{self, Colour, Frame}};
+
+type Point = (i32, i32);
+
+fn heightmap_from_file(path: &str) -> (HashMap<Point, i32>, Point, Point) {
+    heightmap_from_str(&std::fs::read_to_string(path).unwrap())
+}
+
+fn heightmap_from_str(input: &str) -> (HashMap<Point, i32>, Point, Point) {
+    let mut start: Option<(i32, i32)> = None;
+    let mut end: Option<(i32, i32)> = None;
+    let mut heightmap = HashMap::new();
+
+    Input::new(input).grid()
+        .for_each(|(x, y, char)| {
+            let height = match char {
+                'S' => {
+                    start = Some((x as i32, y as i32));
+                    0
+                },
+                'E' => {
+                    end = Some((x as i32, y as i32));
+                    25
+                },
+                c   => c as i32 - 'a' as i32,
+            };
+            heightmap.insert((x as i32, y as i32), height);
+        });
+
+    (heightmap, start.unwrap(), end.unwrap())
+}
+
+pub fn min_distances_to_end(heightmap: &HashMap<(i32, i32), i32>, end: (i32, i32)) -> HashMap<(i32, i32), i32> {
+    let max_x = heightmap.keys().map(|&(x, _)| x).max().unwrap();
+    let max_y = heightmap.keys().map(|&(_, y)| y).max().unwrap();
+    let mut distances_to_end = HashMap::new();
+    distances_to_end.insert(end, 0);
+
+    let mut stack = vec![end];
+
+    while let Some(point@(px, py)) = stack.pop() {
+        let height = heightmap[&point];
+        let distance = distances_to_end[&point];
+        let bordering: Vec<_> = [(-1, 0), (1, 0), (0, -1), (0, 1)].iter()
+            .map(|(dx, dy)| (px + dx, py + dy))
+            .filter(|&(x, y)| x >= 0 && x <= max_x && y >= 0 && y <= max_y)
+            .filter(|b| heightmap[b] >= height - 1)
+            .collect();
+        bordering.iter().for_each(|b| {
+            let existing_distance = distances_to_end.get(b);
+            if existing_distance.map(|&d| distance + 1 < d).unwrap_or(true) {
+                distances_to_end.insert(*b, distance + 1);
+                stack.push(*b);
+            }
+        })
+    }
+
+    distances_to_end
+}
+
+#[allow(dead_code)]
+pub fn part_1() -> i32 {
+    part_1_from_str(&std::fs::read_to_string("input/2022/real/12.txt").unwrap())
+}
+
+pub fn part_1_from_str(input: &str) -> i32 {
+    let (heightmap, start, end) = heightmap_from_str(input);
+    let distances_to_end = min_distances_to_end(&heightmap, end);
+    distances_to_end[&start]
+}
+
+#[allow(dead_code)]
+pub fn part_2() -> i32 {
+    part_2_from_str(&std::fs::read_to_string("input/2022/real/12.txt").unwrap())
+}
+
+pub fn part_2_from_str(input: &str) -> i32 {
+    let (heightmap, _, end) = heightmap_from_str(input);
+    let distances_to_end = min_distances_to_end(&heightmap, end);
+    *heightmap.iter()
+        .filter(|(_, h)| **h == 0)
+        .filter_map(|(p, _)| distances_to_end.get(p))
+        .min().unwrap()
+}
+
+const PATH: u8 = 26;
+const START: u8 = 27;
+const END: u8 = 28;
+
+// a (dark) to z (light) heights, then the route, start and end
+pub const PALETTE: [Colour; 29] = {
+    let mut palette = [[0; 3]; 29];
+    let mut height = 0;
+    while height < 26 {
+        let shade = 30 + height as u8 * 8;
+        palette[height] = [shade / 2, shade, shade / 3];
+        height += 1;
+    }
+    palette[PATH as usize] = [220, 40, 40];
+    palette[START as usize] = [60, 120, 255];
+    palette[END as usize] = [255, 220, 0];
+    palette
+};
+
+// Walk downhill through the distances, always taking a step that gets one closer to the end
+fn route_to_end(heightmap: &HashMap<Point, i32>, distances_to_end: &HashMap<Point, i32>, start: Point) -> Vec<Point> {
+    let mut route = vec![start];
+    let mut point = start;
+    while distances_to_end[&point] > 0 {
+        let distance = distances_to_end[&point];
+        let height = heightmap[&point];
+        point = [(-1, 0), (1, 0), (0, -1), (0, 1)].iter()
+            .map(|(dx, dy)| (point.0 + dx, point.1 + dy))
+            .find(|next| heightmap.get(next).map(|&h| h <= height + 1).unwrap_or(false)
+                && distances_to_end.get(next) == Some(&(distance - 1)))
+            .unwrap();
+        route.push(point);
+    }
+    route
+}
+
+pub fn frames(path: &str, count: usize) -> Vec<Frame> {
+    let (heightmap, start, end) = heightmap_from_file(path);
+    let distances_to_end = min_distances_to_end(&heightmap, end);
+    let route = route_to_end(&heightmap, &distances_to_end, start);
+
+    let width = heightmap.keys().map(|&(x, _)| x).max().unwrap() as usize + 1;
+    let height = heightmap.keys().map(|&(_, y)| y).max().unwrap() as usize + 1;
+    let mut frame = Frame::new(width, height);
+    heightmap.iter().for_each(|(&(x, y), &h)| frame.set(x as usize, y as usize, h as u8));
+    frame.set(start.0 as usize, start.1 as usize, START);
+    frame.set(end.0 as usize, end.1 as usize, END);
+
+    // the start and end keep their own colours
+    let steps = &route[1..route.len().max(2) - 1];
+    let stride = render::stride(steps.len(), count);
+    let mut frames = vec![frame.clone()];
+    for (i, &(x, y)) in steps.iter().enumerate() {
+        frame.set(x as usize, y as usize, PATH);
+        if (i + 1).is_multiple_of(stride) {
+            frames.push(frame.clone());
+        }
+    }
+    if !steps.len().is_multiple_of(stride) {
+        frames.push(frame);
+    }
+    frames
+}
+
+// A random heightmap `size` wide, with a climbable route along the top edge and down the right-hand side
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(26);
+    let height = (size / 2).max(1);
+    let mut grid: Vec<Vec<u8>> = (0..height)
+        .map(|_| (0..width).map(|_| b'a' + rng.below(26) as u8).collect())
+        .collect();
+
+    let route_length = width + height - 1;
+    let route = (0..width).map(|x| (x, 0)).chain((1..height).map(|y| (width - 1, y)));
+    for (i, (x, y)) in route.enumerate() {
+        grid[y][x] = b'a' + (i * 25 / (route_length - 1)) as u8;
+    }
+    grid[0][0] = b'S';
+    grid[height - 1][width - 1] = b'E';
+
+    grid.into_iter().map(|row| String::from_utf8(row).unwrap() + "\n").collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+    use crate::check::differential;
+
+    // Plain breadth-first search forwards from a single starting point
+    fn steps_to_end(heightmap: &HashMap<Point, i32>, start: Point, end: Point) -> Option<i32> {
+        let mut steps = HashMap::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(point) = queue.pop_front() {
+            if point == end {
+                return Some(steps[&point]);
+            }
+            for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                let next = (point.0 + dx, point.1 + dy);
+                let climbable = heightmap.get(&next).map(|&h| h <= heightmap[&point] + 1).unwrap_or(false);
+                if climbable && !steps.contains_key(&next) {
+                    steps.insert(next, steps[&point] + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn part_1_matches_breadth_first_search() {
+        differential(generate, 26..=60, 50, part_1_from_str, |input| {
+            let (heightmap, start, end) = heightmap_from_str(input);
+            steps_to_end(&heightmap, start, end)
+        });
+    }
+
+    #[test]
+    fn part_2_matches_searching_from_every_lowest_point() {
+        differential(generate, 26..=40, 20, part_2_from_str, |input| {
+            let (heightmap, _, end) = heightmap_from_str(input);
+            heightmap.iter()
+                .filter(|(_, h)| **h == 0)
+                .filter_map(|(&p, _)| steps_to_end(&heightmap, p, end))
+                .min()
+        });
+    }
+}
@@ -6,6 +6,7 @@ enum PacketElement {
 
 use std::cmp::Ordering;
 
+use crate::{generate::Rng, input::Input};
 use PacketElement::*;
 
 // Some(bool) means we know the answer, None means we need to continue to next element
@@ -17,10 +18,7 @@ fn compare_elements(left: &PacketElement, right: &PacketElement) -> Option<bool>
         (L(l), L(r)) => {
             l.iter().zip(r.iter())
                 .find_map(|(l, r)| compare_elements(l, r))
-                .or_else(|| {
-                    if l.len() == r.len() { None }
-                    else { Some(l.len() < r.len()) }
-                })
+                .or(if l.len() == r.len() { None } else { Some(l.len() < r.len()) })
         }
     }
 }
@@ -45,7 +43,7 @@ fn parse_packet(line: &str) -> PacketElement {
             },
             ',' => rest = &rest[1..],
             _ => {
-                let num_end = rest.find(&[',', ']']).unwrap();
+                let num_end = rest.find([',', ']']).unwrap();
                 let (num, remaining) = rest.split_at(num_end);
                 stack.last_mut().unwrap().push(I(num.parse().unwrap()));
                 rest = remaining;
@@ -54,18 +52,21 @@ fn parse_packet(line: &str) -> PacketElement {
     }
 }
 
-fn packet_pairs_from_file(path: &str) -> Vec<(PacketElement, PacketElement)> {
-    std::fs::read_to_string(path).unwrap()
-        .split("\n\n")
+fn packet_pairs_from_str(input: &str) -> Vec<(PacketElement, PacketElement)> {
+    Input::new(input).blocks()
         .map(|pair_lines| {
-            let (line_1, line_2) = pair_lines.split_once("\n").unwrap();
-            (parse_packet(line_1), parse_packet(line_2))
+            let mut lines = pair_lines.lines();
+            (parse_packet(lines.next().unwrap()), parse_packet(lines.next().unwrap()))
         }).collect()
 }
 
 #[allow(dead_code)]
 pub fn part_1() -> usize {
-    let pairs = packet_pairs_from_file("input/real/13.txt");
+    part_1_from_str(&std::fs::read_to_string("input/2022/real/13.txt").unwrap())
+}
+
+pub fn part_1_from_str(input: &str) -> usize {
+    let pairs = packet_pairs_from_str(input);
     pairs.into_iter()
         .map(|(l, r)| compare_elements(&l, &r))
         .enumerate()
@@ -75,7 +76,11 @@ pub fn part_1() -> usize {
 
 #[allow(dead_code)]
 pub fn part_2() -> usize {
-    let pairs = packet_pairs_from_file("input/real/13.txt");
+    part_2_from_str(&std::fs::read_to_string("input/2022/real/13.txt").unwrap())
+}
+
+pub fn part_2_from_str(input: &str) -> usize {
+    let pairs = packet_pairs_from_str(input);
     let marker_1 = L(vec![L(vec![I(2)])]);
     let marker_2 = L(vec![L(vec![I(6)])]);
     let mut all_packets: Vec<_> = pairs.into_iter()
@@ -93,3 +98,25 @@ pub fn part_2() -> usize {
     let end = all_packets.iter().position(|e| *e == marker_2).unwrap();
     (start + 1) * (end + 1)
 }
+
+fn random_packet(rng: &mut Rng, depth: usize) -> String {
+    let items: Vec<String> = (0..rng.below(5))
+        .map(|_| if depth > 0 && rng.chance(0.3) {
+            random_packet(rng, depth - 1)
+        } else {
+            rng.below(11).to_string()
+        }).collect();
+    format!("[{}]", items.join(","))
+}
+
+// `size` pairs of packets, never two that compare equal since then neither is in the right order
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let pairs: Vec<String> = (0..size)
+        .map(|_| loop {
+            let (left, right) = (random_packet(rng, 4), random_packet(rng, 4));
+            if compare_elements(&parse_packet(&left), &parse_packet(&right)).is_some() {
+                return format!("{left}\n{right}\n");
+            }
+        }).collect();
+    pairs.join("\n")
+}
@@ -0,0 +1,212 @@
+use std::collections::HashSet;
+
+use crate::{generate::Rng, input::Input, params::params, render::{self, Colour, Frame}};
+
+params! {
+    source_x: i32 = 500, 500;
+    source_y: i32 = 0, 0;
+}
+
+// Coordinates are read as i32 and worked on as i64, so sand can spread as far from the source as it likes without
+// overflowing
+type Point = (i64, i64);
+
+impl Params {
+    fn source(&self) -> Point {
+        (self.source_x.into(), self.source_y.into())
+    }
+}
+
+fn cave_from_file(path: &str) -> HashSet<Point> {
+    cave_from_str(&std::fs::read_to_string(path).unwrap())
+}
+
+fn cave_from_str(input: &str) -> HashSet<Point> {
+    let mut cave = HashSet::new();
+
+    Input::new(input)
+        .lines()
+        .for_each(|line| {
+            let path: Vec<Point> = line.split(" -> ")
+                .map(|coord| {
+                    let (x, y) = coord.split_once(",").unwrap();
+                    (x.parse::<i32>().unwrap().into(), y.parse::<i32>().unwrap().into())
+                }).collect();
+            cave.extend(coords_from_path(&path));
+        });
+    
+    cave
+}
+
+// includes dupes lol
+fn coords_from_path(path: &[Point]) -> impl Iterator<Item=Point> + '_ {
+    path.windows(2)
+        .flat_map(|start_and_end| {
+            let (x0, y0) = start_and_end[0];
+            let (x1, y1) = start_and_end[1];
+            let range;
+            let f: Box<dyn Fn(i64) -> Point>;
+            if x0 == x1 {
+                range = if y0 < y1 { y0..=y1 } else { y1..=y0 };
+                f = Box::new(move |y| (x0, y));
+            } else { // y0 == y1
+                range = if x0 < x1 { x0..=x1 } else { x1..=x0 };
+                f = Box::new(move |x| (x, y0));
+            }
+            range.map(f)
+        })
+}
+
+fn add_a_sand_and_see_where_it_lands(cave: &mut HashSet<Point>, (mut x, mut y): Point, bailout_y: i64) -> Option<Point> {
+    loop {
+        if y >= bailout_y {
+            return None;
+        } else if !cave.contains(&(x, y + 1)) {
+            y += 1;
+        } else if !cave.contains(&(x - 1, y + 1)) {
+            x -= 1;
+            y += 1;
+        } else if !cave.contains(&(x + 1, y + 1)) {
+            x += 1;
+            y += 1;
+        } else {
+            cave.insert((x, y));
+            return Some((x, y));
+        }
+    }
+}
+
+fn add_a_sand_but_now_the_void_is_made_of_floor(cave: &mut HashSet<Point>, (mut x, mut y): Point, floor_y: i64) {
+    loop {
+        if y + 1 == floor_y {
+            cave.insert((x, y));
+            return;
+        } else if !cave.contains(&(x, y + 1)) {
+            y += 1;
+        } else if !cave.contains(&(x - 1, y + 1)) {
+            x -= 1;
+            y += 1;
+        } else if !cave.contains(&(x + 1, y + 1)) {
+            x += 1;
+            y += 1;
+        } else {
+            cave.insert((x, y));
+            return;
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub fn part_1() -> usize {
+    part_1_from_str(&std::fs::read_to_string("input/2022/real/14.txt").unwrap(), &Params::default())
+}
+
+pub fn part_1_from_str(input: &str, params: &Params) -> usize {
+    let mut cave = cave_from_str(input);
+    let bailout_y = cave.iter().map(|(_, y)| y).max().unwrap() + 1;
+    let mut count = 0;
+
+    while add_a_sand_and_see_where_it_lands(&mut cave, params.source(), bailout_y).is_some() {
+        count += 1;
+    }
+
+    count
+}
+
+#[allow(dead_code)]
+pub fn part_2() -> usize {
+    part_2_from_str(&std::fs::read_to_string("input/2022/real/14.txt").unwrap(), &Params::default())
+}
+
+pub fn part_2_from_str(input: &str, params: &Params) -> usize {
+    let mut cave = cave_from_str(input);
+    let floor_y = cave.iter().map(|(_, y)| y).max().unwrap() + 2;
+    let mut count = 0;
+
+    while !cave.contains(&params.source()) {
+        add_a_sand_but_now_the_void_is_made_of_floor(&mut cave, params.source(), floor_y);
+        count += 1;
+    }
+
+    count
+}
+
+const ROCK: u8 = 1;
+const SAND: u8 = 2;
+const SOURCE: u8 = 3;
+
+pub const PALETTE: [Colour; 4] = [
+    [20, 20, 30],    // air
+    [120, 110, 100], // rock
+    [230, 200, 120], // sand
+    [255, 80, 40],   // source
+];
+
+// Sand piling up until it starts falling into the void, as in part 1
+pub fn frames(path: &str, count: usize) -> Vec<Frame> {
+    let rocks = cave_from_file(path);
+    let source = Params::default().source();
+    let bailout_y = rocks.iter().map(|(_, y)| y).max().unwrap() + 1;
+
+    let mut cave = rocks.clone();
+    let mut sand = vec![];
+    while let Some(grain) = add_a_sand_and_see_where_it_lands(&mut cave, source, bailout_y) {
+        sand.push(grain);
+    }
+
+    let min_x = cave.iter().map(|&(x, _)| x).min().unwrap().min(source.0);
+    let max_x = cave.iter().map(|&(x, _)| x).max().unwrap().max(source.0);
+    let mut frame = Frame::new((max_x - min_x + 1) as usize, bailout_y as usize);
+    let draw = |frame: &mut Frame, (x, y): Point, cell| frame.set((x - min_x) as usize, y as usize, cell);
+    rocks.iter().for_each(|&rock| draw(&mut frame, rock, ROCK));
+    draw(&mut frame, source, SOURCE);
+
+    let stride = render::stride(sand.len(), count);
+    let mut frames = vec![frame.clone()];
+    for (i, &grain) in sand.iter().enumerate() {
+        draw(&mut frame, grain, SAND);
+        if (i + 1).is_multiple_of(stride) {
+            frames.push(frame.clone());
+        }
+    }
+    if !sand.len().is_multiple_of(stride) {
+        frames.push(frame);
+    }
+    frames
+}
+
+// `size` rock paths of alternating horizontal and vertical lines, spread out under the sand source
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let spread = 10 + size as i64 * 2;
+    let depth = 10 + size as i64;
+    (0..size)
+        .map(|_| {
+            let mut point = (500 + rng.range(-spread..=spread), rng.range(2..=depth));
+            let mut points = vec![point];
+            for i in 0..rng.range(1..=4) {
+                let length = rng.range(1..=8) * if rng.chance(0.5) { 1 } else { -1 };
+                if i % 2 == 0 {
+                    point.0 += length;
+                } else {
+                    point.1 = (point.1 + length).max(1);
+                }
+                points.push(point);
+            }
+            points.iter().map(|(x, y)| format!("{x},{y}")).collect::<Vec<_>>().join(" -> ") + "\n"
+        }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A ledge at the very bottom of the i32 range, where the void and the floor are both past i32::MAX
+    #[test]
+    fn rock_at_the_bottom_of_i32() {
+        let input = format!("0,{max} -> 2,{max}\n", max = i32::MAX);
+        let params = Params { source_x: 1, source_y: i32::MAX - 3 };
+        assert_eq!(part_1_from_str(&input, &params), 1);
+        // five rows of the pile down to the floor, less the ledge and the spot in its shadow
+        assert_eq!(part_2_from_str(&input, &params), 25 - 4);
+    }
+}
@@ -0,0 +1,228 @@
+use std::collections::{HashSet};
+
+use crate::{generate::Rng, input::Input, params::params};
+
+params! {
+    target_y: i32 = 2_000_000, 10;
+    border_max: i32 = 4_000_000, 20;
+}
+
+// sensor, closest beacon. Coordinates are read as i32 and worked on as i64, so distances and the edges of each
+// sensor's range can't overflow
+type Reading = ((i64, i64), (i64, i64));
+
+fn sensor_readings_from_str(input: &str) -> Vec<Reading> {
+    Input::new(input)
+        .lines()
+        .map(|line| {
+            let (sensor_x, rest) = line[12..].split_once(',').unwrap();
+            let (sensor_y, rest) = rest[3..].split_once(':').unwrap();
+            let (beacon_x, rest) = rest[24..].split_once(',').unwrap();
+            let beacon_y = &rest[3..];
+            let coord = |s: &str| s.parse::<i32>().unwrap().into();
+            (
+                (coord(sensor_x), coord(sensor_y)),
+                (coord(beacon_x), coord(beacon_y)),
+            )
+        }).collect()
+}
+
+pub fn overlap(a: (i64, i64), b: (i64, i64)) -> Option<(i64, i64)> {
+    if a.0.max(b.0) <= a.1.min(b.1) {
+        Some((a.0.min(b.0), a.1.max(b.1)))
+    } else {
+        None
+    }
+}
+
+pub fn merge_zones(zones: &[(i64, i64)]) -> HashSet<(i64, i64)> {
+    let mut merged_zones: HashSet<(i64, i64)> = HashSet::new();
+
+    for &z in zones {
+        let mut new_zone = z;
+        let mut to_remove = vec![];
+        for &m in &merged_zones {
+            if let Some(merged) = overlap(new_zone, m) {
+                to_remove.push(m);
+                new_zone = merged;
+            }
+        }
+        to_remove.into_iter().for_each(|r| { merged_zones.remove(&r); });
+        merged_zones.insert(new_zone);
+    }
+
+    merged_zones
+}
+
+#[allow(dead_code)]
+pub fn part_1() -> i64 {
+    part_1_from_str(&std::fs::read_to_string("input/2022/real/15.txt").unwrap(), &Params::default())
+}
+
+pub fn part_1_from_str(input: &str, params: &Params) -> i64 {
+    positions_without_beacon(&sensor_readings_from_str(input), params.target_y.into())
+}
+
+fn positions_without_beacon(readings: &[Reading], target_y: i64) -> i64 {
+    let sensor_zones: Vec<_> = readings.iter()
+        .filter_map(|&((sx, sy), (bx, by))| {
+            let distance = (bx - sx).abs() + (by - sy).abs();
+            let x_radius = distance - (target_y - sy).abs();
+            if x_radius >= 0 {
+                Some((sx - x_radius, sx + x_radius))
+            } else {
+                None
+            }
+        }).collect();
+
+    let merged = merge_zones(&sensor_zones);
+
+    let beacons_in_merged_at_target_y = readings.iter()
+        .filter_map(|&(_, b)|
+            if b.1 == target_y { Some(b.0) } else { None }
+        ).filter(|&x| {
+            merged.iter().any(|&z| z.0 <= x && x <= z.1)
+        }).collect::<HashSet<_>>().len() as i64;
+
+    let coverage: i64 = merged.into_iter().map(|z| z.1 - z.0 + 1).sum();
+
+    coverage - beacons_in_merged_at_target_y
+}
+
+fn in_range_of_any_sensors(sensors: &[((i64, i64), i64)], point: (i64, i64)) -> bool {
+    sensors.iter().any(move |&(centre, range)| (centre.0 - point.0).abs() + (centre.1 - point.1).abs() <= range)
+}
+
+fn just_beyond_the_border((centre, range): ((i64, i64), i64)) -> impl Iterator<Item=(i64, i64)> {
+    let nw = (0..=range+1).map(move |i| (centre.0 - range - 1 + i, centre.1 + i));
+    let ne = (0..=range+1).map(move |i| (centre.0 + range + 1 - i, centre.1 + i));
+    let sw = (0..=range+1).map(move |i| (centre.0 - range - 1 + i, centre.1 - i));
+    let se = (0..=range+1).map(move |i| (centre.0 + range + 1 - i, centre.1 - i));
+    nw.chain(ne).chain(sw).chain(se)
+}
+
+#[allow(dead_code)]
+pub fn part_2() -> i64 {
+    part_2_from_str(&std::fs::read_to_string("input/2022/real/15.txt").unwrap(), &Params::default())
+}
+
+pub fn part_2_from_str(input: &str, params: &Params) -> i64 {
+    tuning_frequency(&sensor_readings_from_str(input), params.border_max.into())
+}
+
+fn tuning_frequency(readings: &[Reading], border_max: i64) -> i64 {
+
+    // centre, border distance
+    let sensor_zones: Vec<((i64, i64), i64)> = readings.iter()
+        .map(|&((sx, sy), (bx, by))| ((sx, sy), (bx - sx).abs() + (by - sy).abs()))
+        .collect();
+    
+    let beacon: (i64, i64) = sensor_zones.iter()
+        .flat_map(|&sensor| just_beyond_the_border(sensor))
+        .filter(|&(x, y)| x >= 0 && x <= border_max && y >= 0 && y <= border_max)
+        .find(|&point| !in_range_of_any_sensors(&sensor_zones, point))
+        .unwrap();
+    
+    beacon.0 * 4_000_000 + beacon.1
+}
+
+// `size` sensors around four huge ones that cover everything in the part 2 search area bar one gap
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    sensor_field(rng, size, 4_000_000)
+}
+
+// The huge sensors sit diagonally out from the gap, each reaching everything in its quadrant except the gap itself,
+// and none of the others are allowed to reach it
+fn sensor_field(rng: &mut Rng, size: usize, border_max: i64) -> String {
+    let gap = (rng.range(1..=border_max - 1), rng.range(1..=border_max - 1));
+    let mut readings = vec![
+        ((gap.0 + border_max, gap.1 + border_max), (gap.0 + 1, gap.1)),
+        ((gap.0 - border_max, gap.1 + border_max), (gap.0, gap.1 + 1)),
+        ((gap.0 - border_max, gap.1 - border_max), (gap.0 - 1, gap.1)),
+        ((gap.0 + border_max, gap.1 - border_max), (gap.0, gap.1 - 1)),
+    ];
+    while readings.len() < size + 4 {
+        let sensor = (rng.range(0..=border_max), rng.range(0..=border_max));
+        let to_gap = (sensor.0 - gap.0).abs() + (sensor.1 - gap.1).abs();
+        if to_gap < 2 {
+            continue;
+        }
+        let range = rng.range(1..=to_gap - 1);
+        let dx = rng.range(-range..=range);
+        let dy = (range - dx.abs()) * if rng.chance(0.5) { 1 } else { -1 };
+        readings.push((sensor, (sensor.0 + dx, sensor.1 + dy)));
+    }
+    rng.shuffle(&mut readings);
+
+    readings.into_iter()
+        .map(|((sx, sy), (bx, by))| format!("Sensor at x={sx}, y={sy}: closest beacon is at x={bx}, y={by}\n"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::differential;
+
+    fn distance(a: (i64, i64), b: (i64, i64)) -> i64 {
+        (a.0 - b.0).abs() + (a.1 - b.1).abs()
+    }
+
+    fn covered(readings: &[Reading], point: (i64, i64)) -> bool {
+        readings.iter().any(|&(sensor, beacon)| distance(sensor, point) <= distance(sensor, beacon))
+    }
+
+    // Check every point along the row that any sensor could possibly reach
+    fn reference_part_1(readings: &[Reading], target_y: i64) -> i64 {
+        let reach = readings.iter().map(|&(s, b)| distance(s, b)).max().unwrap();
+        let min_x = readings.iter().map(|&((x, _), _)| x).min().unwrap() - reach;
+        let max_x = readings.iter().map(|&((x, _), _)| x).max().unwrap() + reach;
+        (min_x..=max_x)
+            .filter(|&x| covered(readings, (x, target_y)))
+            .filter(|&x| readings.iter().all(|&(_, beacon)| beacon != (x, target_y)))
+            .count() as i64
+    }
+
+    // Check every point in the search area
+    fn reference_part_2(readings: &[Reading], border_max: i64) -> Option<i64> {
+        (0..=border_max)
+            .flat_map(|x| (0..=border_max).map(move |y| (x, y)))
+            .find(|&point| !covered(readings, point))
+            .map(|(x, y)| x * 4_000_000 + y)
+    }
+
+    #[test]
+    fn part_1_matches_checking_every_position() {
+        for target_y in [0, 7, 20, 33] {
+            differential(
+                |rng, size| sensor_field(rng, size, 40),
+                0..=20,
+                50,
+                |input| positions_without_beacon(&sensor_readings_from_str(input), target_y),
+                |input| Some(reference_part_1(&sensor_readings_from_str(input), target_y)),
+            );
+        }
+    }
+
+    #[test]
+    fn part_2_matches_checking_every_position() {
+        differential(
+            |rng, size| sensor_field(rng, size, 40),
+            0..=20,
+            50,
+            |input| tuning_frequency(&sensor_readings_from_str(input), 40),
+            |input| reference_part_2(&sensor_readings_from_str(input), 40),
+        );
+    }
+
+    #[test]
+    fn ranges_reaching_past_i32() {
+        let input = format!("Sensor at x={}, y=0: closest beacon is at x={}, y=0\n", i32::MIN, i32::MAX);
+        let reach = i32::MAX as i64 - i32::MIN as i64;
+        assert_eq!(positions_without_beacon(&sensor_readings_from_str(&input), 0), 2 * reach);
+
+        let input = format!("Sensor at x={max}, y={max}: closest beacon is at x={max}, y={}\n", i32::MAX - 1, max = i32::MAX);
+        let max = i32::MAX as i64;
+        assert_eq!(tuning_frequency(&sensor_readings_from_str(&input), max), (max - 2) * 4_000_000 + max);
+    }
+}
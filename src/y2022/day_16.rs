@@ -0,0 +1,370 @@
+use std::{collections::{HashMap, VecDeque}};
+
+use crate::{cancel::{self, Unsolved}, generate::Rng, input::Input, params::params, progress, trace::{debug, trace}};
+
+params! {
+    minutes_alone: i32 = 30, 30;
+    minutes_with_elephant: i32 = 26, 26;
+}
+
+// Flow rates, adjacencies, starter node
+fn valve_network_from_str(input: &str) -> (Vec<i64>, Vec<Vec<usize>>, usize) {
+    let data: Vec<_> = Input::new(input)
+        .lines()
+        .map(|line| {
+            let parts: Vec<_> = line.split(&['=',';']).collect();
+            let name = parts[0][6..8].to_owned();
+            let flow_rate: i64 = parts[1].parse().unwrap();
+            let tunnels: Vec<_> = if parts[2].as_bytes()[22] as char == 's' {
+                parts[2][24..].split(", ").map(str::to_owned).collect()
+            } else {
+                parts[2][23..].split(", ").map(str::to_owned).collect()
+            };
+            (name, flow_rate, tunnels)
+        }).collect();
+    
+    let names_to_index: HashMap<_, _> = data.iter().enumerate().map(|(i, (name, _, _))| (name.clone(), i)).collect();
+    let adjacencies: Vec<Vec<usize>> = data.iter()
+        .map(|(_, _, adjacents)|
+            adjacents.iter().map(|t| names_to_index[t]).collect()
+        ).collect();
+    let rates: Vec<i64> = data.iter().map(|(_, rate, _)| *rate).collect();
+
+    (rates, adjacencies, names_to_index["AA"])
+}
+
+fn shortest_path_lengths(adjacencies: &[Vec<usize>], nodes_of_interest: &[usize]) -> HashMap<(usize, usize), usize> {
+    let mut paths = HashMap::new();
+    for (i, &start) in nodes_of_interest.iter().enumerate() {
+        for &end in &nodes_of_interest[i+1..] {
+            let shortest_path_length = get_a_shortest_path(adjacencies, start, end).unwrap().len() - 1; // don't count the start node
+            paths.insert((start, end), shortest_path_length);
+            paths.insert((end, start), shortest_path_length);
+        }
+    }
+    paths
+}
+
+fn get_a_shortest_path(neighbours: &[Vec<usize>], start: usize, target: usize) -> Option<Vec<usize>> {
+    let mut path_stack: VecDeque<Vec<usize>> = VecDeque::new();
+    path_stack.push_back(vec![start]);
+
+    loop {
+        let mut path = path_stack.pop_front()?;
+        let end = *path.last().unwrap();
+        for &next in &neighbours[end] {
+            if next == target {
+                path.push(next);
+                return Some(path);
+            }
+            if !path.contains(&next) {
+                let mut option = path.clone();
+                option.push(next);
+                path_stack.push_back(option);
+            }
+        }
+    }
+}
+
+// Valves are tracked by index in a u64, so only the first 64 can have any flow
+fn useful_valves(rates: &[i64]) -> Result<u64, Unsolved> {
+    rates.iter().enumerate()
+        .filter(|&(_, r)| *r > 0)
+        .try_fold(0u64, |mask, (i, _)| Some(mask | 1u64.checked_shl(i.try_into().ok()?)?))
+        .ok_or(Unsolved::Overflow)
+}
+
+// No order of valves can beat having them all open from the first minute, so once that fits in an i64 none of the
+// running totals can overflow either
+fn check_pressure_fits(rates: &[i64], minutes: i32) -> Result<(), Unsolved> {
+    rates.iter().copied()
+        .filter(|&r| r > 0)
+        .try_fold(0, i64::checked_add)
+        .and_then(|all_open| all_open.checked_mul(minutes.into()))
+        .map(|_| ())
+        .ok_or(Unsolved::Overflow)
+}
+
+fn lookup_array_from_pair_map<T: Clone>(map: HashMap<(usize, usize), T>, size: usize, default: T) -> Vec<T> {
+    let mut array = vec![default.clone(); size * size];
+    for ((start, end), value) in map.into_iter() {
+        array[size * start + end] = value
+    }
+    array
+}
+
+fn biggest_vent_pressure(
+    rates: &[i64],              // flow rates
+    path_lengths_array: &[usize],  // distance between nodes. indexed [node1 * nodecount + node2]
+    v: usize,                   // current node
+    tick: i64,                  // current minute, counts down from start
+    total: i64,                 // total impact of vents opened so far
+    remaining_mask: u64,        // bitmask of useful vents that are still closed
+    max: &mut i64,              // pointer to store highest total impact
+) -> Result<(), Unsolved> {
+    cancel::check()?;
+    progress::explored(1);
+    let mut in_progress_mask = remaining_mask;
+    while in_progress_mask.leading_zeros() < 64 {
+        let next_vent = 63 - in_progress_mask.leading_zeros();
+        in_progress_mask &= !(1 << next_vent);
+        let distance = if next_vent == v as u32 { 0 }
+            else { path_lengths_array[v * rates.len() + next_vent as usize] };
+        let new_tick = tick - distance as i64 - 1;
+        if new_tick > 0 {
+            let new_total = total + new_tick * rates[next_vent as usize];
+            let new_mask = remaining_mask & !(1 << next_vent);
+            if remaining_mask == 0 {
+                *max = (*max).max(total);
+            } else {
+                biggest_vent_pressure(rates, path_lengths_array, next_vent as usize, new_tick, new_total, new_mask, max)?;
+            }
+        } else {
+            trace!("pruned {v} -> {next_vent}: {distance} away with {tick} minutes left");
+        }
+    }
+    *max = (*max).max(total);
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn build_vent_set_pressures(
+    target_mask: u64,                       // bitmask of all the useful vents
+    rates: &[i64],                          // flow rates
+    path_lengths_array: &[usize],              // distance between nodes. indexed [node1 * nodecount + node2]
+    v: usize,                               // current node
+    tick: i64,                              // current minute, counts down from start
+    total: i64,                             // total impact of vents opened so far
+    remaining_mask: u64,                    // bitmask of useful vents that are still closed
+    vent_options: &mut HashMap<u64, i64>,   // complete map of possible (non-optimal) vent sets used to maximum impact
+) -> Result<(), Unsolved> {
+    cancel::check()?;
+    progress::explored(1);
+    let mut in_progress_mask = remaining_mask;
+    vent_options.entry(target_mask & !remaining_mask)
+        .and_modify(|e| *e = (*e).max(total))
+        .or_insert(total);
+    while in_progress_mask.leading_zeros() < 64 {
+        let next = 63 - in_progress_mask.leading_zeros();
+        in_progress_mask &= !(1 << next);
+        let distance = if next == v as u32 { 0 }
+            else { path_lengths_array[v * rates.len() + next as usize] };
+        let new_tick = tick - distance as i64 - 1;
+        if new_tick > 0 {
+            let new_total = total + new_tick * rates[next as usize];
+            let new_mask = remaining_mask & !(1 << next);
+            if remaining_mask == 0 {
+                vent_options.entry(target_mask & !remaining_mask)
+                    .and_modify(|e| *e = (*e).max(total))
+                    .or_insert(total);
+            } else {
+                build_vent_set_pressures(target_mask, rates, path_lengths_array, next as usize, new_tick, new_total, new_mask, vent_options)?;
+            }
+        } 
+    }
+    Ok(())
+}
+
+fn fastest_team_effort(vent_set_pressures: &HashMap<u64, i64>) -> Result<i64, Unsolved> {
+    let array: Vec<_> = vent_set_pressures.iter().collect();
+
+    let mut max = 0;
+    for (i, &(mask_1, max_1)) in array.iter().enumerate() {
+        cancel::check()?;
+        for &(mask_2, max_2) in &array[i + 1..] {
+            if mask_1 & mask_2 == 0 {
+                max = max.max(max_1 + max_2);
+            }
+        }
+    }
+    Ok(max)
+}
+
+#[allow(dead_code)]
+pub fn part_1() -> Result<i64, Unsolved> {
+    part_1_from_str(&std::fs::read_to_string("input/2022/real/16.txt").unwrap(), &Params::default())
+}
+
+pub fn part_1_from_str(input: &str, params: &Params) -> Result<i64, Unsolved> {
+    let (rates, adjacencies, starter) = valve_network_from_str(input);
+
+    // Find the shortest distances between nodes of interest (non-zero flow-rate valves and the starting valve)
+    let targets_plus_starter: Vec<_> = rates.iter().enumerate().filter(|&(i, r)| *r > 0 || i == starter).map(|(i, _)| i).collect();
+    let path_lengths = shortest_path_lengths(&adjacencies, &targets_plus_starter);
+    let path_lengths_array = lookup_array_from_pair_map(path_lengths, rates.len(), 0);
+
+    // The set of valves that we're interested in visiting (non-zero flow-rate)
+    let target_mask = useful_valves(&rates)?;
+    check_pressure_fits(&rates, params.minutes_alone)?;
+
+    // Visit every order of target valves we can in the time we have, saving the largest vent result we see
+    let mut max = 0;
+    let _progress = progress::begin("trying orders of valves", None);
+    biggest_vent_pressure(&rates, &path_lengths_array, starter, params.minutes_alone.into(), 0, target_mask, &mut max)?;
+    debug!("{} useful valves, best pressure {max}", target_mask.count_ones());
+    Ok(max)
+}
+
+#[allow(dead_code)]
+pub fn part_2() -> Result<i64, Unsolved> {
+    part_2_from_str(&std::fs::read_to_string("input/2022/real/16.txt").unwrap(), &Params::default())
+}
+
+pub fn part_2_from_str(input: &str, params: &Params) -> Result<i64, Unsolved> {
+    let (rates, adjacencies, starter) = valve_network_from_str(input);
+
+    // Find the shortest distances between nodes of interest (non-zero flow-rate valves and the starting valve)
+    let targets_plus_starter: Vec<_> = rates.iter().enumerate().filter(|&(i, r)| *r > 0 || i == starter).map(|(i, _)| i).collect();
+    let path_lengths = shortest_path_lengths(&adjacencies, &targets_plus_starter);
+    let path_lengths_array = lookup_array_from_pair_map(path_lengths, rates.len(), 0);
+
+    // The set of valves that we're interested in visiting (non-zero flow-rate)
+    let target_mask = useful_valves(&rates)?;
+    check_pressure_fits(&rates, params.minutes_with_elephant)?;
+
+    // Visit every order of target valves we can in the time left after teaching the elephant (**including** partial combinations - important),
+    // saving the max vent volume possible for each set of target valves
+    let mut vent_set_pressures: HashMap<u64, i64> = HashMap::new();
+    let _progress = progress::begin("finding the best pressure for each set of valves", None);
+    build_vent_set_pressures(target_mask, &rates, &path_lengths_array, starter, params.minutes_with_elephant.into(), 0, target_mask, &mut vent_set_pressures)?;
+    debug!("{} reachable sets of open valves out of {}", vent_set_pressures.len(), 1u64 << target_mask.count_ones());
+
+    // Find the two non-intersecting vent sets with the highest total vent volume
+    // Non-intersecting vent sets enforces that the elephant and I don't open the same valve
+    fastest_team_effort(&vent_set_pressures)
+}
+
+// `size` useful valves plus as many broken ones, all connected up by a random spanning tree and some extra tunnels.
+// Valves are tracked in a u64 mask by index, so this stops at 31 useful valves
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let useful = size.min(31);
+    let count = useful * 2 + 1;
+
+    let mut names = vec!["AA".to_owned()];
+    while names.len() < count {
+        let name: String = (0..2).map(|_| (b'A' + rng.below(26) as u8) as char).collect();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    let rates: Vec<i64> = (0..count).map(|i| if i >= 1 && i <= useful { rng.range(1..=25) } else { 0 }).collect();
+
+    let mut tunnels: Vec<Vec<usize>> = vec![vec![]; count];
+    let connect = |tunnels: &mut Vec<Vec<usize>>, a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for valve in 1..count {
+        connect(&mut tunnels, rng.below(valve), valve);
+    }
+    for _ in 0..count / 2 {
+        connect(&mut tunnels, rng.below(count), rng.below(count));
+    }
+
+    let mut order: Vec<usize> = (0..count).collect();
+    rng.shuffle(&mut order);
+    order.into_iter()
+        .map(|valve| {
+            let leads_to: Vec<&str> = tunnels[valve].iter().map(|&t| names[t].as_str()).collect();
+            let tunnels = if leads_to.len() == 1 { "tunnel leads to valve" } else { "tunnels lead to valves" };
+            format!("Valve {} has flow rate={}; {} {}\n", names[valve], rates[valve], tunnels, leads_to.join(", "))
+        }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::check::differential;
+
+    // Minute by minute: every agent either opens the valve it's at or walks down a tunnel.
+    // Memoised on (minutes left, where everyone is, which valves are open)
+    fn most_pressure(
+        rates: &[i64],
+        adjacencies: &[Vec<usize>],
+        minutes: i32,
+        positions: Vec<usize>,
+        open: u64,
+        memo: &mut HashMap<(i32, Vec<usize>, u64), i64>,
+    ) -> i64 {
+        if minutes == 0 {
+            return 0;
+        }
+        if let Some(&best) = memo.get(&(minutes, positions.clone(), open)) {
+            return best;
+        }
+
+        // every combination of moves, carried along with the valves opened and pressure gained so far
+        let mut options: Vec<(Vec<usize>, u64, i64)> = vec![(vec![], open, 0)];
+        for &position in &positions {
+            let mut next_options = vec![];
+            for (moved, open, gained) in options {
+                if rates[position] > 0 && open & (1 << position) == 0 {
+                    let opened = [moved.clone(), vec![position]].concat();
+                    next_options.push((opened, open | 1 << position, gained + rates[position] * i64::from(minutes - 1)));
+                }
+                for &next in &adjacencies[position] {
+                    next_options.push(([moved.clone(), vec![next]].concat(), open, gained));
+                }
+            }
+            options = next_options;
+        }
+
+        let best = options.into_iter()
+            .map(|(mut moved, open, gained)| {
+                moved.sort_unstable();
+                gained + most_pressure(rates, adjacencies, minutes - 1, moved, open, memo)
+            })
+            .max().unwrap();
+        memo.insert((minutes, positions, open), best);
+        best
+    }
+
+    fn reference(input: &str, minutes: i32, agents: usize) -> i64 {
+        let (rates, adjacencies, starter) = valve_network_from_str(input);
+        most_pressure(&rates, &adjacencies, minutes, vec![starter; agents], 0, &mut HashMap::new())
+    }
+
+    #[test]
+    fn part_1_matches_walking_every_minute() {
+        let params = Params::default();
+        differential(generate, 1..=5, 30, |input| part_1_from_str(input, &params), |input| Some(Ok(reference(input, 30, 1))));
+    }
+
+    #[test]
+    fn part_2_matches_walking_every_minute_together() {
+        let params = Params::default();
+        differential(generate, 1..=4, 15, |input| part_2_from_str(input, &params), |input| Some(Ok(reference(input, 26, 2))));
+    }
+
+    #[test]
+    fn pressure_past_i32_is_counted() {
+        let input = "Valve AA has flow rate=0; tunnels lead to valves BB, CC\n\
+                     Valve BB has flow rate=100000000; tunnel leads to valve AA\n\
+                     Valve CC has flow rate=100000000; tunnel leads to valve AA\n";
+        // BB open for 28 minutes, then CC for 25 each way; in part 2 we take one each and have 24 minutes apiece
+        assert_eq!(part_1_from_str(input, &Params::default()), Ok(5_300_000_000));
+        assert_eq!(part_2_from_str(input, &Params::default()), Ok(4_800_000_000));
+    }
+
+    #[test]
+    fn pressure_past_i64_overflows() {
+        let input = format!("Valve AA has flow rate=0; tunnel leads to valve BB\nValve BB has flow rate={}; tunnel leads to valve AA\n", i64::MAX / 2);
+        assert_eq!(part_1_from_str(&input, &Params::default()), Err(Unsolved::Overflow));
+        assert_eq!(part_2_from_str(&input, &Params::default()), Err(Unsolved::Overflow));
+    }
+
+    #[test]
+    fn a_spent_deadline_times_out() {
+        let input = "Valve AA has flow rate=0; tunnels lead to valves BB, CC\n\
+                     Valve BB has flow rate=1; tunnel leads to valve AA\n\
+                     Valve CC has flow rate=1; tunnel leads to valve AA\n";
+        let timed_out = cancel::with_timeout(Some(Duration::ZERO), || {
+            (part_1_from_str(input, &Params::default()), part_2_from_str(input, &Params::default()))
+        });
+        assert_eq!(timed_out, (Err(Unsolved::TimedOut), Err(Unsolved::TimedOut)));
+    }
+}
@@ -0,0 +1,279 @@
+use std::{collections::{HashSet, hash_map::DefaultHasher}, hash::{Hasher, Hash}};
+
+use crate::{cancel::{self, Unsolved}, generate::Rng, input::Input, params::params, progress, render::{Colour, Frame}, trace::debug};
+
+params! {
+    part_1_rocks: u64 = 2022, 2022;
+    part_2_rocks: u64 = 1_000_000_000_000, 1_000_000_000_000;
+}
+
+#[derive(Clone, PartialEq, Eq)]
+enum Jet { L, R, }
+
+fn jets_from_file(path: &str) -> Vec<Jet> {
+    jets_from_str(&std::fs::read_to_string(path).unwrap())
+}
+
+fn jets_from_str(input: &str) -> Vec<Jet> {
+    Input::new(input).text().chars()
+        .map(|c| if c == '<' { Jet::L } else { Jet::R })
+        .collect()
+}
+
+// Heights only go up by a few rows a rock, so an i64 has room for more rocks than anything could drop
+type Coord = (i64, i64);
+
+#[derive(Clone)]
+struct Rock { cs: &'static [Coord], h: i64 }
+
+const ROCKS: [Rock; 5] = [
+        Rock { cs: &[(0, 0), (1, 0), (2, 0), (3, 0)        ], h: 1},
+        Rock { cs: &[(0, 1), (1, 0), (1, 1), (1, 2), (2, 1)], h: 3},
+        Rock { cs: &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)], h: 3},
+        Rock { cs: &[(0, 0), (0, 1), (0, 2), (0, 3),       ], h: 4},
+        Rock { cs: &[(0, 0), (0, 1), (1, 0), (1, 1)        ], h: 2},
+];
+
+// Drop a rock from its spawn point until it settles, returning where it landed and how many jets it took
+fn drop_rock<'a>(settled: &mut HashSet<Coord>, rock: &Rock, jets: &mut impl Iterator<Item = &'a Jet>, highest: i64) -> (Coord, usize) {
+    let mut rock_x = 2;
+    let mut rock_y = highest + 4;
+    let mut jets_used = 0;
+
+    loop {
+        let jet = jets.next().unwrap();
+        jets_used += 1;
+        let dx = if *jet == Jet::L { -1 } else { 1 };
+
+        rock_x = shift_sideways(settled, rock, rock_x, rock_y, dx);
+
+        let drop_obstructed = rock.cs.iter()
+            .map(|&(ox, oy)| (rock_x + ox, rock_y + oy - 1))
+            .any(|c| settled.contains(&c));
+        if drop_obstructed {
+            settled.extend(rock.cs.iter().map(|&(ox, oy)| (rock_x + ox, rock_y + oy)));
+            return ((rock_x, rock_y), jets_used);
+        } else {
+            rock_y -= 1;
+        }
+    }
+}
+
+fn shift_sideways(settled: &HashSet<Coord>, rock: &Rock, x: i64, y: i64, dx: i64) -> i64 {
+    let can_move = rock.cs.iter()
+        .map(|&(ox, oy)| (x + ox + dx, y + oy))
+        .all(|(rx, ry)| (0..7).contains(&rx) && !settled.contains(&(rx, ry)));
+    if can_move { x + dx } else { x }
+}
+
+#[allow(dead_code)]
+pub fn part_1() -> Result<i64, Unsolved> {
+    part_1_from_str(&std::fs::read_to_string("input/2022/real/17.txt").unwrap(), &Params::default())
+}
+
+pub fn part_1_from_str(input: &str, params: &Params) -> Result<i64, Unsolved> {
+    let jets = jets_from_str(input);
+    let mut jets = jets.iter().cycle();
+    let mut rocks = ROCKS.into_iter().cycle();
+
+    let mut settled_rocks: HashSet<Coord> = HashSet::new();
+    (0..=6).for_each(|x| { settled_rocks.insert((x, 0)); });
+
+    let mut highest = 0;
+
+    let _progress = progress::begin("dropping rocks", Some(params.part_1_rocks));
+    for _ in 0..params.part_1_rocks {
+        cancel::check()?;
+        progress::advance(1);
+        let rock = rocks.next().unwrap();
+        let ((_, rock_y), _) = drop_rock(&mut settled_rocks, &rock, &mut jets, highest);
+        highest = highest.max(rock_y + rock.h - 1);
+    }
+
+    Ok(highest)
+}
+
+fn hash_top_structure(settled: &HashSet<Coord>, top: i64, height: i64) -> u64 {
+    let mut h = DefaultHasher::new();
+    let mut map: Vec<bool> = vec![];
+    for y in top - height..top {
+        for x in 0..7 {
+            map.push(settled.contains(&(x, y)));
+        }
+    }
+    map.hash(&mut h);
+    h.finish()
+}
+
+#[allow(dead_code)]
+pub fn part_2() -> Result<u64, Unsolved> {
+    part_2_from_str(&std::fs::read_to_string("input/2022/real/17.txt").unwrap(), &Params::default())
+}
+
+pub fn part_2_from_str(input: &str, params: &Params) -> Result<u64, Unsolved> {
+    tower_height(&jets_from_str(input), params.part_2_rocks)
+}
+
+// Simulate a good while to settle into a cycle, then skip ahead by whole cycles. Overflow if the tower ends up too tall
+// for a u64
+fn tower_height(jet_list: &[Jet], total_rocks: u64) -> Result<u64, Unsolved> {
+    let mut jets = jet_list.iter().cycle();
+    let mut rocks = ROCKS.into_iter().cycle();
+
+    let mut settled_rocks: HashSet<Coord> = HashSet::new();
+    (0..=6).for_each(|x| { settled_rocks.insert((x, 0)); });
+
+    let mut highest = 0;
+
+    let mut jets_used = 0;
+    let mut rocks_used = 0;
+
+    // jet, rock, hash of structure, height
+    let mut patterns: Vec<(usize, usize, u64, i64)> = vec![];
+
+    let warm_up = total_rocks.min(10000);
+    let _progress = progress::begin("dropping rocks to find a cycle", Some(warm_up));
+    for _ in 0..warm_up {
+        cancel::check()?;
+        progress::advance(1);
+        let rock = rocks.next().unwrap();
+        rocks_used += 1;
+        let ((_, rock_y), jets_taken) = drop_rock(&mut settled_rocks, &rock, &mut jets, highest);
+        jets_used += jets_taken;
+        highest = highest.max(rock_y + rock.h - 1);
+
+        let jet_i = jets_used % jet_list.len();
+        let rock_i = rocks_used % ROCKS.len();
+        let structure = hash_top_structure(&settled_rocks, highest, 20);
+        patterns.push((jet_i, rock_i, structure, highest));
+    }
+
+    if rocks_used as u64 == total_rocks {
+        return Ok(highest as u64);
+    }
+
+    let last = patterns.last().unwrap();
+    let (cycle_len_minus_1, prev) = patterns[0..patterns.len()-1].iter().rev()
+        .enumerate()
+        .find(|&(_, e)| last.0 == e.0 && last.1 == e.1 && last.2 == e.2).unwrap();
+
+    let cycle_len = cycle_len_minus_1 + 1;
+    let cycle_height = last.3 - prev.3;
+    let remaining = total_rocks - rocks_used as u64;
+    let cycles = remaining / cycle_len as u64;
+    let remaining = remaining % cycle_len as u64;
+    debug!("cycle of {cycle_len} rocks adding {cycle_height} rows (jet {}, rock {}), skipping {cycles} cycles and dropping {remaining} more", last.0, last.1);
+
+    for _ in 0..remaining {
+        cancel::check()?;
+        let rock = rocks.next().unwrap();
+        let ((_, rock_y), _) = drop_rock(&mut settled_rocks, &rock, &mut jets, highest);
+        highest = highest.max(rock_y + rock.h - 1);
+    }
+
+    (cycle_height as u64).checked_mul(cycles)
+        .and_then(|skipped| skipped.checked_add(highest as u64))
+        .ok_or(Unsolved::Overflow)
+}
+
+const WALL: u8 = 1;
+
+// air, walls and floor, then one colour per rock shape
+pub const PALETTE: [Colour; 7] = [
+    [15, 15, 25],
+    [90, 90, 90],
+    [230, 90, 60],
+    [240, 200, 60],
+    [90, 200, 90],
+    [70, 140, 240],
+    [190, 100, 220],
+];
+
+// The tower after each of the first `count` rocks, drawn upright with the floor at the bottom
+pub fn frames(path: &str, count: usize) -> Vec<Frame> {
+    let jet_list = jets_from_file(path);
+    let mut jets = jet_list.iter().cycle();
+
+    let mut settled_rocks: HashSet<Coord> = HashSet::new();
+    (0..=6).for_each(|x| { settled_rocks.insert((x, 0)); });
+
+    let mut highest = 0;
+    let mut landings = vec![];
+    for (shape, rock) in ROCKS.iter().enumerate().cycle().take(count) {
+        let landing = drop_rock(&mut settled_rocks, rock, &mut jets, highest).0;
+        highest = highest.max(landing.1 + rock.h - 1);
+        landings.push((shape, landing));
+    }
+
+    // walls either side of the 7-wide chamber
+    let height = highest as usize + 1;
+    let mut frame = Frame::new(9, height);
+    for y in 0..height {
+        frame.set(0, y, WALL);
+        frame.set(8, y, WALL);
+    }
+    (1..8).for_each(|x| frame.set(x, height - 1, WALL));
+
+    let mut frames = vec![frame.clone()];
+    for (shape, (x, y)) in landings {
+        for &(ox, oy) in ROCKS[shape].cs {
+            frame.set((x + ox + 1) as usize, height - 1 - (y + oy) as usize, WALL + 1 + shape as u8);
+        }
+        frames.push(frame.clone());
+    }
+    frames
+}
+
+// A jet pattern `size` long
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let jets: String = (0..size.max(1)).map(|_| *rng.choose(&['<', '>'])).collect();
+    jets + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::check::differential;
+
+    // Drop every last rock
+    fn reference(jet_list: &[Jet], total_rocks: u64) -> u64 {
+        let mut jets = jet_list.iter().cycle();
+        let mut settled_rocks: HashSet<Coord> = (0..=6).map(|x| (x, 0)).collect();
+        let mut highest = 0;
+        for rock in ROCKS.iter().cycle().take(total_rocks as usize) {
+            let ((_, rock_y), _) = drop_rock(&mut settled_rocks, rock, &mut jets, highest);
+            highest = highest.max(rock_y + rock.h - 1);
+        }
+        highest as u64
+    }
+
+    #[test]
+    fn tower_height_matches_dropping_every_rock() {
+        for total_rocks in [1, 2022, 10_377] {
+            differential(
+                generate,
+                1..=40,
+                3,
+                |input| tower_height(&jets_from_str(input), total_rocks),
+                |input| Some(Ok(reference(&jets_from_str(input), total_rocks))),
+            );
+        }
+    }
+
+    #[test]
+    fn towers_past_u64_overflow() {
+        let params = Params { part_2_rocks: u64::MAX, ..Params::default() };
+        assert_eq!(part_2_from_str("<>\n", &params), Err(Unsolved::Overflow));
+    }
+
+    #[test]
+    fn a_spent_deadline_times_out() {
+        let params = Params { part_1_rocks: u64::MAX, ..Params::default() };
+        let timed_out = cancel::with_timeout(Some(Duration::ZERO), || {
+            (part_1_from_str("<>\n", &params), part_2_from_str("<>\n", &params))
+        });
+        assert_eq!(timed_out, (Err(Unsolved::TimedOut), Err(Unsolved::TimedOut)));
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::HashSet;
+
+use crate::{generate::Rng, input::Input};
+
+// Coordinates are read as i32 and worked on as i64, so the neighbours of a cube at the edge of the range still exist
+type Cube = (i64, i64, i64);
+
+fn scan_from_str(input: &str) -> Vec<Cube> {
+    Input::new(input)
+        .lines()
+        .map(|line| {
+            let mut coords = line.split(',').map(|c| c.parse::<i32>().unwrap().into());
+            (
+                coords.next().unwrap(),
+                coords.next().unwrap(),
+                coords.next().unwrap()
+            )
+        }).collect()
+}
+
+fn set_neighbours(cube: Cube, neighbours: &mut [Cube; 6]) {
+    neighbours[0] = (cube.0 - 1, cube.1    , cube.2    );
+    neighbours[1] = (cube.0 + 1, cube.1    , cube.2    );
+    neighbours[2] = (cube.0    , cube.1 - 1, cube.2    );
+    neighbours[3] = (cube.0    , cube.1 + 1, cube.2    );
+    neighbours[4] = (cube.0    , cube.1    , cube.2 - 1);
+    neighbours[5] = (cube.0    , cube.1    , cube.2 + 1);
+}
+
+#[allow(dead_code)]
+pub fn part_1() -> usize {
+    part_1_from_str(&std::fs::read_to_string("input/2022/real/18.txt").unwrap())
+}
+
+pub fn part_1_from_str(input: &str) -> usize {
+    let scan = scan_from_str(input);
+    let cube_set: HashSet<Cube> = HashSet::from_iter(scan);
+    let mut neighbours = [(0, 0, 0); 6];
+    cube_set.iter()
+        .map(|cube| {
+            set_neighbours(*cube, &mut neighbours);
+            neighbours.iter().filter(|n| !cube_set.contains(n)).count()
+        }).sum()
+}
+
+fn out_of_bounds(bounds: ((i64, i64), (i64, i64), (i64, i64)), cube: Cube) -> bool {
+    cube.0 < bounds.0.0 || cube.0 > bounds.0.1 ||
+        cube.1 < bounds.1.0 || cube.1 > bounds.1.1 ||
+        cube.2 < bounds.2.0 || cube.2 > bounds.2.1
+}
+
+fn classify(
+    body: &HashSet<Cube>,
+    body_bounds: ((i64, i64), (i64, i64), (i64, i64)),
+    inside: &mut HashSet<Cube>,
+    outside: &mut HashSet<Cube>,
+    classifee: Cube,
+) {
+    let mut seen: HashSet<Cube> = HashSet::new();
+    let mut stack: Vec<Cube> = vec![classifee];
+
+    while let Some(cube) = stack.pop() {
+        if body.contains(&cube) {
+            continue
+        } else if out_of_bounds(body_bounds, cube) {
+            seen.insert(cube);
+            outside.extend(seen);
+            return;
+        } else if outside.contains(&cube) {
+            outside.extend(seen);
+            return;
+        } else if inside.contains(&cube) {
+            inside.extend(seen);
+            return;
+        } else {
+            let mut neighbours = [(0, 0, 0); 6];
+            set_neighbours(cube, &mut neighbours);
+            seen.insert(cube);
+            stack.extend(neighbours.into_iter().filter(|n| !seen.contains(n)));
+        }
+    }
+    
+    // we've flood-filled the space without going out of bounds, must be inside
+    inside.extend(seen);
+}
+
+#[allow(dead_code)]
+pub fn part_2() -> usize {
+    part_2_from_str(&std::fs::read_to_string("input/2022/real/18.txt").unwrap())
+}
+
+pub fn part_2_from_str(input: &str) -> usize {
+    let scan = scan_from_str(input);
+    let body: HashSet<Cube> = HashSet::from_iter(scan);
+    let mut inside: HashSet<Cube> = HashSet::new();
+    let mut outside: HashSet<Cube> = HashSet::new();
+
+    let min_x = body.iter().map(|&c| c.0).min().unwrap();
+    let max_x = body.iter().map(|&c| c.0).max().unwrap();
+    let min_y = body.iter().map(|&c| c.1).min().unwrap();
+    let max_y = body.iter().map(|&c| c.1).max().unwrap();
+    let min_z = body.iter().map(|&c| c.2).min().unwrap();
+    let max_z = body.iter().map(|&c| c.2).max().unwrap();
+    let bounds = ((min_x, max_x), (min_y, max_y), (min_z, max_z));
+
+    let mut neighbours = [(0, 0, 0); 6];
+
+    body.iter()
+        .for_each(|cube| {
+            set_neighbours(*cube, &mut neighbours);
+            neighbours.iter().for_each(|&neighbour| {
+                classify(
+                    &body,
+                    bounds,
+                    &mut inside,
+                    &mut outside,
+                    neighbour
+                );
+            });
+        });
+
+    body.iter()
+        .map(|cube| {
+            set_neighbours(*cube, &mut neighbours);
+            neighbours.iter().filter(|n| outside.contains(n)).count()
+        }).sum()
+}
+
+// `size` distinct cubes packed into a box about twice their volume, so there are air pockets to find
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = ((size * 2) as f64).cbrt().ceil() as i64 + 1;
+    let mut seen = HashSet::new();
+    let mut cubes = vec![];
+    while cubes.len() < size {
+        let cube = (rng.range(1..=side), rng.range(1..=side), rng.range(1..=side));
+        if seen.insert(cube) {
+            cubes.push(cube);
+        }
+    }
+    cubes.into_iter().map(|(x, y, z)| format!("{x},{y},{z}\n")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::differential;
+
+    fn touching(a: Cube, b: Cube) -> bool {
+        (a.0 - b.0).abs() + (a.1 - b.1).abs() + (a.2 - b.2).abs() == 1
+    }
+
+    // Six faces per cube, minus two for every pair of cubes that touch
+    fn reference_part_1(scan: &[Cube]) -> usize {
+        let touching_pairs = scan.iter().enumerate()
+            .flat_map(|(i, &a)| scan[i + 1..].iter().filter(move |&&b| touching(a, b)))
+            .count();
+        scan.len() * 6 - touching_pairs * 2
+    }
+
+    // Flood fill the air from a corner of a box one bigger than the droplet all round,
+    // counting the faces of the droplet the air runs into
+    fn reference_part_2(scan: &[Cube]) -> usize {
+        let body: HashSet<Cube> = scan.iter().copied().collect();
+        let min = |f: fn(&Cube) -> i64| scan.iter().map(f).min().unwrap() - 1;
+        let max = |f: fn(&Cube) -> i64| scan.iter().map(f).max().unwrap() + 1;
+        let bounds = ((min(|c| c.0), max(|c| c.0)), (min(|c| c.1), max(|c| c.1)), (min(|c| c.2), max(|c| c.2)));
+
+        let corner = (bounds.0.0, bounds.1.0, bounds.2.0);
+        let mut air = HashSet::from([corner]);
+        let mut stack = vec![corner];
+        let mut faces = 0;
+        let mut neighbours = [(0, 0, 0); 6];
+        while let Some(cube) = stack.pop() {
+            set_neighbours(cube, &mut neighbours);
+            for &n in &neighbours {
+                if body.contains(&n) {
+                    faces += 1;
+                } else if !out_of_bounds(bounds, n) && air.insert(n) {
+                    stack.push(n);
+                }
+            }
+        }
+        faces
+    }
+
+    #[test]
+    fn part_1_matches_comparing_every_pair() {
+        differential(generate, 1..=200, 50, part_1_from_str, |input| Some(reference_part_1(&scan_from_str(input))));
+    }
+
+    #[test]
+    fn part_2_matches_flooding_from_outside() {
+        differential(generate, 1..=200, 50, part_2_from_str, |input| Some(reference_part_2(&scan_from_str(input))));
+    }
+
+    #[test]
+    fn cubes_at_the_edges_of_i32() {
+        let input = format!("{max},{max},{max}\n{max},{max},{}\n", i32::MAX - 1, max = i32::MAX);
+        assert_eq!(part_1_from_str(&input), 10);
+        assert_eq!(part_2_from_str(&input), 10);
+        let input = format!("{min},{min},{min}\n", min = i32::MIN);
+        assert_eq!(part_1_from_str(&input), 6);
+        assert_eq!(part_2_from_str(&input), 6);
+    }
+}
@@ -0,0 +1,689 @@
+use std::{cmp::Ordering, fmt, fs, str::FromStr};
+
+use crate::{cancel::Unsolved, generate::Rng, input::Input, params::params};
+
+params! {
+    game: Game = Game::rock_paper_scissors(), Game::rock_paper_scissors();
+}
+
+// How a round ends, from our side
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    fn from_input(c: char) -> Outcome {
+        match c {
+            'X' => Outcome::Lose,
+            'Y' => Outcome::Draw,
+            'Z' => Outcome::Win,
+            _ => unreachable!(),
+        }
+    }
+}
+
+// A tournament where every move beats exactly half of the others, so no move is better than any other. Rock Paper
+// Scissors is the smallest that's any fun. Moves are numbered in the order they were given, which is also the order of
+// their letters in the strategy guide
+#[derive(Clone, Debug)]
+pub struct Game {
+    names: Vec<String>,
+    // beats[a][b] when a beats b
+    beats: Vec<Vec<bool>>,
+    selection_scores: Vec<u64>,
+    // for losing, drawing and winning
+    outcome_scores: [u64; 3],
+}
+
+impl Game {
+    // Each move beats the ones an odd number of places before it, going round, which for three moves is Rock Paper
+    // Scissors. Selection scores count up from 1 and a round is worth 0, 3 or 6
+    pub fn cyclic(names: &[&str]) -> Game {
+        let n = names.len();
+        assert!(!n.is_multiple_of(2), "a cyclic game needs an odd number of moves");
+        let beats = (0..n)
+            .map(|a| (0..n).map(|b| (a + n - b) % n % 2 == 1).collect())
+            .collect();
+        Game {
+            names: names.iter().map(|&name| name.to_owned()).collect(),
+            beats,
+            selection_scores: (1..=n as u64).collect(),
+            outcome_scores: [0, 3, 6],
+        }
+    }
+
+    pub fn rock_paper_scissors() -> Game {
+        Game::cyclic(&["rock", "paper", "scissors"])
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> Game {
+        Game::cyclic(&["rock", "paper", "scissors", "spock", "lizard"])
+    }
+
+    // A dominance table, one move to a line in letter order, with an optional line of outcome scores:
+    //   # comments and blank lines are skipped
+    //   outcomes 0 3 6
+    //   rock 1 beats scissors
+    //   paper 2 beats rock
+    //   scissors 3 beats paper
+    pub fn from_table(table: &str) -> Result<Game, String> {
+        let mut outcome_scores = [0, 3, 6];
+        let mut moves: Vec<(&str, u64, Vec<&str>)> = vec![];
+        let table = Input::new(table);
+        for line in table.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let score = |field: &str| field.parse::<u64>().map_err(|_| format!("not a score: {field}"));
+            match fields.as_slice() {
+                ["outcomes", lose, draw, win] => outcome_scores = [score(lose)?, score(draw)?, score(win)?],
+                [name, selection, "beats", losers @ ..] => moves.push((name, score(selection)?, losers.to_vec())),
+                _ => return Err(format!("expected `name score beats name...` or `outcomes lose draw win`: {line}")),
+            }
+        }
+
+        let index = |name: &str| moves.iter().position(|&(n, _, _)| n == name).ok_or(format!("no move called {name}"));
+        let n = moves.len();
+        let mut beats = vec![vec![false; n]; n];
+        for (a, (name, _, losers)) in moves.iter().enumerate() {
+            if index(name)? != a {
+                return Err(format!("{name} is in the table twice"));
+            }
+            for &loser in losers {
+                beats[a][index(loser)?] = true;
+            }
+        }
+        let game = Game {
+            names: moves.iter().map(|&(name, _, _)| name.to_owned()).collect(),
+            beats,
+            selection_scores: moves.iter().map(|&(_, score, _)| score).collect(),
+            outcome_scores,
+        };
+        game.check()?;
+        Ok(game)
+    }
+
+    // Every pair of moves settled one way, and every move beating half of the others
+    fn check(&self) -> Result<(), String> {
+        let n = self.size();
+        if n.is_multiple_of(2) || n > 26 {
+            return Err(format!("a game needs an odd number of moves, at most 26, not {n}"));
+        }
+        for a in 0..n {
+            if self.beats[a][a] {
+                return Err(format!("{} beats itself", self.names[a]));
+            }
+            for b in a + 1..n {
+                if self.beats[a][b] == self.beats[b][a] {
+                    let how = if self.beats[a][b] { "beat each other" } else { "don't beat each other" };
+                    return Err(format!("{} and {} {how}", self.names[a], self.names[b]));
+                }
+            }
+            let wins = self.beats[a].iter().filter(|&&beats| beats).count();
+            if wins != n / 2 {
+                return Err(format!("{} beats {wins} moves rather than {}", self.names[a], n / 2));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn size(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn name(&self, m: usize) -> &str {
+        &self.names[m]
+    }
+
+    pub fn selection_score(&self, m: usize) -> u64 {
+        self.selection_scores[m]
+    }
+
+    pub fn outcome(&self, us: usize, them: usize) -> Outcome {
+        if self.beats[us][them] { Outcome::Win }
+            else if us == them { Outcome::Draw }
+            else { Outcome::Lose }
+    }
+
+    // What playing `us` against `them` is worth to us
+    pub fn score(&self, us: usize, them: usize) -> Option<u64> {
+        self.selection_score(us).checked_add(self.outcome_scores[self.outcome(us, them) as usize])
+    }
+
+    // The move that gets `outcome` against `them`, and when there's a choice (any game bigger than three moves), the one
+    // scoring most
+    pub fn choose(&self, them: usize, outcome: Outcome) -> usize {
+        (0..self.size())
+            .filter(|&us| self.outcome(us, them) == outcome)
+            .max_by_key(|&us| (self.selection_score(us), std::cmp::Reverse(us)))
+            .unwrap()
+    }
+
+    // Their column counts from A
+    fn them_from_input(&self, c: char) -> usize {
+        let m = (c as u8).wrapping_sub(b'A') as usize;
+        assert!(m < self.size(), "{c} isn't one of the {} moves", self.size());
+        m
+    }
+
+    // Ours counts from X, going round to A after Z. Anything that isn't a capital letter lands past the 26 moves a game
+    // can have
+    fn us_from_input(&self, c: char) -> usize {
+        let letter = (c as u8).wrapping_sub(b'A') as usize;
+        let m = if letter < 26 { (letter + 3) % 26 } else { letter };
+        assert!(m < self.size(), "{c} isn't one of the {} moves", self.size());
+        m
+    }
+}
+
+// "rps" and "rpsls" for the built in games, or the path of a dominance table
+impl FromStr for Game {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Game, String> {
+        match s {
+            "rps" => Ok(Game::rock_paper_scissors()),
+            "rpsls" => Ok(Game::rock_paper_scissors_lizard_spock()),
+            path => Game::from_table(&fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?),
+        }
+    }
+}
+
+fn strategy_from_str(input: &str) -> Vec<(char, char)> {
+    Input::new(input).lines()
+        .map(|line| {
+            let them = line.as_bytes()[0] as char;
+            let us = line.as_bytes()[2] as char;
+            (them, us)
+        })
+        .collect()
+}
+
+// Every round scored with `us` picking our move against theirs from the second column. A u64 total would need billions
+// of billions of rounds to overflow, where an i32 only needs a few hundred million, but a game's own scores can be as
+// big as they like
+fn total(game: &Game, strategy: &[(char, char)], us: impl Fn(usize, char) -> usize) -> Result<u64, Unsolved> {
+    strategy.iter()
+        .map(|&(them, second)| {
+            let them = game.them_from_input(them);
+            game.score(us(them, second), them)
+        })
+        .try_fold(0, |total: u64, score| total.checked_add(score?))
+        .ok_or(Unsolved::Overflow)
+}
+
+#[allow(dead_code)]
+pub fn part_1() -> Result<u64, Unsolved> {
+    part_1_from_str(&std::fs::read_to_string("input/2022/real/2.txt").unwrap(), &Params::default())
+}
+
+pub fn part_1_from_str(input: &str, params: &Params) -> Result<u64, Unsolved> {
+    let game = &params.game;
+    total(game, &strategy_from_str(input), |_, us| game.us_from_input(us))
+}
+
+#[allow(dead_code)]
+pub fn part_2() -> Result<u64, Unsolved> {
+    part_2_from_str(&std::fs::read_to_string("input/2022/real/2.txt").unwrap(), &Params::default())
+}
+
+pub fn part_2_from_str(input: &str, params: &Params) -> Result<u64, Unsolved> {
+    let game = &params.game;
+    total(game, &strategy_from_str(input), |them, outcome| game.choose(them, Outcome::from_input(outcome)))
+}
+
+const OUTCOMES: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+
+// What X, Y and Z in the second column stand for: three different moves (part 1 guessed rock, paper, scissors) or
+// how the round should end (part 2's lose, draw, win)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cipher {
+    Moves([usize; 3]),
+    Outcomes([Outcome; 3]),
+}
+
+impl Cipher {
+    // Every way X, Y and Z could be three of `game`'s moves, then every way they could be the outcomes
+    pub fn all(game: &Game) -> Vec<Cipher> {
+        let arrangements = |n: usize| (0..n)
+            .flat_map(move |x| (0..n).flat_map(move |y| (0..n).map(move |z| [x, y, z])))
+            .filter(|&[x, y, z]| x != y && y != z && x != z);
+        arrangements(game.size()).map(Cipher::Moves)
+            .chain(arrangements(3).map(|a| Cipher::Outcomes(a.map(|i| OUTCOMES[i]))))
+            .collect()
+    }
+
+    fn us(&self, game: &Game, them: usize, second: char) -> usize {
+        let i = (second as u8).wrapping_sub(b'X') as usize;
+        assert!(i < 3, "{second} isn't X, Y or Z");
+        match self {
+            Cipher::Moves(moves) => moves[i],
+            Cipher::Outcomes(outcomes) => game.choose(them, outcomes[i]),
+        }
+    }
+
+    fn describe(&self, game: &Game) -> String {
+        let meanings: Vec<String> = match self {
+            Cipher::Moves(moves) => moves.iter().map(|&m| game.name(m).to_owned()).collect(),
+            Cipher::Outcomes(outcomes) => outcomes.iter().map(|outcome| format!("{outcome:?}").to_lowercase()).collect(),
+        };
+        format!("X {}, Y {}, Z {}", meanings[0], meanings[1], meanings[2])
+    }
+
+    // Which of the puzzle's parts read the guide this way
+    fn part(&self) -> Option<u32> {
+        match self {
+            Cipher::Moves([0, 1, 2]) => Some(1),
+            Cipher::Outcomes([Outcome::Lose, Outcome::Draw, Outcome::Win]) => Some(2),
+            _ => None,
+        }
+    }
+}
+
+pub struct Decoding {
+    pub cipher: Cipher,
+    pub score: Result<u64, Unsolved>,
+}
+
+// The guide scored under every cipher, with the ones giving `expected` (if we know what the score should be) flagged
+pub struct Decodings {
+    game: Game,
+    pub decodings: Vec<Decoding>,
+    pub expected: Option<u64>,
+}
+
+impl Decodings {
+    fn matches(&self, decoding: &Decoding) -> bool {
+        self.expected.is_some() && decoding.score.ok() == self.expected
+    }
+
+    pub fn matching(&self) -> impl Iterator<Item = &Decoding> {
+        self.decodings.iter().filter(|decoding| self.matches(decoding))
+    }
+}
+
+pub fn decode(input: &str, game: &Game, expected: Option<u64>) -> Decodings {
+    let strategy = strategy_from_str(input);
+    let decodings = Cipher::all(game).into_iter()
+        .map(|cipher| Decoding { cipher, score: total(game, &strategy, |them, second| cipher.us(game, them, second)) })
+        .collect();
+    Decodings { game: game.clone(), decodings, expected }
+}
+
+impl fmt::Display for Decodings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows: Vec<(String, String)> = self.decodings.iter()
+            .map(|decoding| (decoding.cipher.describe(&self.game), match &decoding.score {
+                Ok(score) => score.to_string(),
+                Err(unsolved) => unsolved.to_string(),
+            }))
+            .collect();
+        let width = rows.iter().map(|(cipher, _)| cipher.len()).max().unwrap_or(0);
+        let digits = rows.iter().map(|(_, score)| score.len()).max().unwrap_or(0);
+        for (decoding, (cipher, score)) in self.decodings.iter().zip(&rows) {
+            let part = decoding.cipher.part().map(|part| format!("  part {part}")).unwrap_or_default();
+            let flag = if self.matches(decoding) { "  <- matches" } else { "" };
+            writeln!(f, "{cipher:<width$}  {score:>digits$}{part}{flag}")?;
+        }
+        match self.expected {
+            Some(expected) => match self.matching().count() {
+                0 => writeln!(f, "no reading scores {expected}"),
+                n => writeln!(f, "{n} of {} readings score {expected}", self.decodings.len()),
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+// Who's playing in a tournament. The scripted players follow a column of the guide from a random round on, going
+// back round to the start if a match outlasts the guide
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Player {
+    // their column
+    Opponent,
+    // our column, read as moves the way part 1 reads it
+    Guide,
+    Fixed(usize),
+    // the best answer to everything the other side has played so far
+    FrequencyCounter,
+    // the best way of beating the other side's last move
+    LastMoveBeater,
+}
+
+// What a player has seen of the other side so far, and where in the guide it started
+struct Memory {
+    start: usize,
+    counts: Vec<u64>,
+    last: Option<usize>,
+}
+
+impl Player {
+    fn describe(&self, game: &Game) -> String {
+        match self {
+            Player::Opponent => "opponent".to_owned(),
+            Player::Guide => "guide".to_owned(),
+            Player::Fixed(m) => format!("always {}", game.name(*m)),
+            Player::FrequencyCounter => "frequency counter".to_owned(),
+            Player::LastMoveBeater => "last move beater".to_owned(),
+        }
+    }
+
+    fn play(&self, game: &Game, guide: &[(usize, usize)], round: usize, memory: &Memory, rng: &mut Rng) -> usize {
+        match self {
+            Player::Opponent => guide[(memory.start + round) % guide.len()].0,
+            Player::Guide => guide[(memory.start + round) % guide.len()].1,
+            Player::Fixed(m) => *m,
+            Player::FrequencyCounter => *rng.choose(&best_responses(game, &memory.counts)),
+            Player::LastMoveBeater => match memory.last {
+                Some(last) => game.choose(last, Outcome::Win),
+                None => rng.below(game.size()),
+            },
+        }
+    }
+}
+
+// What always playing `us` scores against a mix of their moves, `counts[them]` of each. The model checks up front that
+// no single round overflows, and adding those up can't get near a u128's limit
+fn score_against(game: &Game, us: usize, counts: &[u64]) -> u128 {
+    counts.iter().enumerate()
+        .map(|(them, &count)| count as u128 * game.score(us, them).unwrap() as u128)
+        .sum()
+}
+
+// The moves scoring most against a mix of their moves: all of them when we haven't seen anything yet
+fn best_responses(game: &Game, counts: &[u64]) -> Vec<usize> {
+    let scores: Vec<u128> = (0..game.size()).map(|us| score_against(game, us, counts)).collect();
+    let best = scores.iter().max().unwrap();
+    (0..game.size()).filter(|&us| scores[us] == *best).collect()
+}
+
+// Each side's average score a round over all the matches, and how many matches each side won
+pub struct Tournament {
+    pub players: [Player; 2],
+    pub means: [f64; 2],
+    pub wins: [usize; 2],
+    pub draws: usize,
+}
+
+fn tournament(game: &Game, guide: &[(usize, usize)], players: [Player; 2], matches: usize, rounds: usize, rng: &mut Rng) -> Tournament {
+    let mut scored = [0u128; 2];
+    let mut wins = [0; 2];
+    let mut draws = 0;
+    for _ in 0..matches {
+        let mut memories = [(); 2].map(|_| Memory { start: rng.below(guide.len()), counts: vec![0; game.size()], last: None });
+        let mut totals = [0u128; 2];
+        for round in 0..rounds {
+            let moves = [0, 1].map(|side| players[side].play(game, guide, round, &memories[side], rng));
+            for side in 0..2 {
+                let (us, them) = (moves[side], moves[1 - side]);
+                totals[side] += game.score(us, them).unwrap() as u128;
+                memories[side].counts[them] += 1;
+                memories[side].last = Some(them);
+            }
+        }
+        match totals[0].cmp(&totals[1]) {
+            Ordering::Greater => wins[0] += 1,
+            Ordering::Less => wins[1] += 1,
+            Ordering::Equal => draws += 1,
+        }
+        scored = [scored[0] + totals[0], scored[1] + totals[1]];
+    }
+    let per_round = (matches * rounds).max(1) as f64;
+    Tournament { players, means: scored.map(|total| total as f64 / per_round), wins, draws }
+}
+
+// What the guide gives away about the opponent: how often they play each move, what always playing each of ours would
+// score against that, and how the guide and a fixed best response hold up against players that adapt
+pub struct Model {
+    game: Game,
+    pub counts: Vec<u64>,
+    // always playing each move, over the whole guide
+    pub totals: Vec<u128>,
+    pub best: usize,
+    // the guide as part 1 reads it
+    pub guide_total: u128,
+    pub matches: usize,
+    pub rounds: usize,
+    pub tournaments: Vec<Tournament>,
+}
+
+// `matches` matches of as many rounds as the guide has in each tournament
+pub fn model(input: &str, game: &Game, matches: usize, seed: u64) -> Result<Model, Unsolved> {
+    let guide: Vec<(usize, usize)> = strategy_from_str(input).into_iter()
+        .map(|(them, us)| (game.them_from_input(them), game.us_from_input(us)))
+        .collect();
+    if guide.is_empty() {
+        return Err(Unsolved::NoSolution);
+    }
+    let n = game.size();
+    if (0..n).any(|us| (0..n).any(|them| game.score(us, them).is_none())) {
+        return Err(Unsolved::Overflow);
+    }
+
+    let mut counts = vec![0; n];
+    for &(them, _) in &guide {
+        counts[them] += 1;
+    }
+    let totals: Vec<u128> = (0..n).map(|us| score_against(game, us, &counts)).collect();
+    let best = best_responses(game, &counts)[0];
+    let guide_total = guide.iter().map(|&(them, us)| game.score(us, them).unwrap() as u128).sum();
+
+    let rng = &mut Rng::new(seed);
+    let tournaments = [
+        [Player::Guide, Player::FrequencyCounter],
+        [Player::Guide, Player::LastMoveBeater],
+        [Player::Fixed(best), Player::Opponent],
+        [Player::FrequencyCounter, Player::Opponent],
+        [Player::LastMoveBeater, Player::Opponent],
+    ].into_iter()
+        .map(|players| tournament(game, &guide, players, matches, guide.len(), rng))
+        .collect();
+    Ok(Model { game: game.clone(), counts, totals, best, guide_total, matches, rounds: guide.len(), tournaments })
+}
+
+impl fmt::Display for Model {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let game = &self.game;
+        let rounds = self.rounds as f64;
+        let mix: Vec<String> = self.counts.iter().enumerate()
+            .map(|(m, &count)| format!("{} {:.1}%", game.name(m), 100.0 * count as f64 / rounds))
+            .collect();
+        writeln!(f, "{} rounds, the opponent playing {}", self.rounds, mix.join(", "))?;
+        for (m, &total) in self.totals.iter().enumerate() {
+            let best = if m == self.best { "  best" } else { "" };
+            writeln!(f, "always {}: {total}, {:.3} a round{best}", game.name(m), total as f64 / rounds)?;
+        }
+        writeln!(f, "the guide: {}, {:.3} a round", self.guide_total, self.guide_total as f64 / rounds)?;
+
+        writeln!(f, "{} matches of {} rounds:", self.matches, self.rounds)?;
+        let names: Vec<[String; 2]> = self.tournaments.iter().map(|t| t.players.map(|p| p.describe(game))).collect();
+        let width = names.iter().map(|[a, b]| a.len() + b.len() + 4).max().unwrap_or(0);
+        for (t, [a, b]) in self.tournaments.iter().zip(names) {
+            writeln!(
+                f,
+                "  {:<width$}  {:.3} to {:.3} a round, {} won {}, lost {}, drew {}",
+                format!("{a} vs {b}"), t.means[0], t.means[1], a, t.wins[0], t.wins[1], t.draws,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// `size` rounds of the strategy guide
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{} {}\n", rng.choose(&['A', 'B', 'C']), rng.choose(&['X', 'Y', 'Z'])))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::differential;
+
+    // The puzzle's scoring spelt out round by round: A/X rock, B/Y paper, C/Z scissors
+    fn reference_part_1(input: &str) -> Option<u64> {
+        Some(input.lines().map(|line| match line {
+            "A X" => 4, "A Y" => 8, "A Z" => 3,
+            "B X" => 1, "B Y" => 5, "B Z" => 9,
+            "C X" => 7, "C Y" => 2, "C Z" => 6,
+            _ => unreachable!(),
+        }).sum())
+    }
+
+    // X lose, Y draw, Z win
+    fn reference_part_2(input: &str) -> Option<u64> {
+        Some(input.lines().map(|line| match line {
+            "A X" => 3, "A Y" => 4, "A Z" => 8,
+            "B X" => 1, "B Y" => 5, "B Z" => 9,
+            "C X" => 2, "C Y" => 6, "C Z" => 7,
+            _ => unreachable!(),
+        }).sum())
+    }
+
+    const RPS_TABLE: &str = "
+        # the puzzle's own game, written out
+        rock 1 beats scissors
+        paper 2 beats rock
+        scissors 3 beats paper
+    ";
+
+    #[test]
+    fn three_moves_are_the_puzzle() {
+        let sample = std::fs::read_to_string("input/2022/sample/2.txt").unwrap();
+        assert_eq!(part_1_from_str(&sample, &Params::default()), Ok(15));
+        assert_eq!(part_2_from_str(&sample, &Params::default()), Ok(12));
+        for game in [Game::rock_paper_scissors(), Game::from_table(RPS_TABLE).unwrap()] {
+            let params = Params { game };
+            differential(generate, 0..=50, 100, |i| part_1_from_str(i, &params).unwrap(), reference_part_1);
+            differential(generate, 0..=50, 100, |i| part_2_from_str(i, &params).unwrap(), reference_part_2);
+        }
+    }
+
+    #[test]
+    fn rock_paper_scissors_lizard_spock() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let m = |name: &str| game.names.iter().position(|n| n == name).unwrap();
+        for (winner, loser) in [
+            ("scissors", "paper"), ("paper", "rock"), ("rock", "lizard"), ("lizard", "spock"), ("spock", "scissors"),
+            ("scissors", "lizard"), ("lizard", "paper"), ("paper", "spock"), ("spock", "rock"), ("rock", "scissors"),
+        ] {
+            assert_eq!(game.outcome(m(winner), m(loser)), Outcome::Win);
+            assert_eq!(game.outcome(m(loser), m(winner)), Outcome::Lose);
+        }
+        assert!(game.check().is_ok());
+
+        // Y is paper beating D's spock and B is lizard drawing with E, then losing to lizard is best done with spock
+        let params = Params { game };
+        assert_eq!(part_1_from_str("D Y\nE B\n", &params), Ok((2 + 6) + (5 + 3)));
+        assert_eq!(part_2_from_str("E X\n", &params), Ok(4));
+    }
+
+    #[test]
+    fn scores_come_from_the_table() {
+        let game = Game::from_table("outcomes 1 10 100\nrock 7 beats scissors\npaper 0 beats rock\nscissors 5 beats paper").unwrap();
+        let params = Params { game };
+        assert_eq!(part_1_from_str("A Y\nB X\nC Z\n", &params), Ok(100 + (7 + 1) + (5 + 10)));
+        assert_eq!(part_2_from_str("A Y\nB X\nC Z\n", &params), Ok((7 + 10) + (7 + 1) + (7 + 100)));
+
+        let game = Game::from_table(&format!("rock {} beats scissors\npaper 2 beats rock\nscissors 3 beats paper", u64::MAX)).unwrap();
+        assert_eq!(part_1_from_str("C X\n", &Params { game }), Err(Unsolved::Overflow));
+    }
+
+    #[test]
+    fn rejects_unfair_tables() {
+        let error = |table: &str| Game::from_table(table).unwrap_err();
+        assert_eq!(error("rock 1 beats paper\npaper 2 beats rock"), "a game needs an odd number of moves, at most 26, not 2");
+        assert_eq!(error("rock 1 beats paper\npaper 2 beats rock\nscissors 3 beats paper"), "rock and paper beat each other");
+        assert_eq!(error("rock 1 beats rock\npaper 2\nscissors 3"), "expected `name score beats name...` or `outcomes lose draw win`: paper 2");
+        assert_eq!(error("rock 1 beats scissors\npaper 2 beats stone\nscissors 3 beats paper"), "no move called stone");
+        assert_eq!(error("rock one beats scissors"), "not a score: one");
+        assert_eq!(error("rock 1 beats\nrock 2 beats\nrock 3 beats"), "rock is in the table twice");
+        assert_eq!(
+            error("a 1 beats b c\nb 1 beats c d\nc 1 beats d e\nd 1 beats e b\ne 1 beats a"),
+            "a and d don't beat each other",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "@ isn't one of the 3 moves")]
+    fn their_moves_start_at_a() {
+        let _ = part_1_from_str("@ X\n", &Params::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ isn't one of the 5 moves")]
+    fn our_moves_stop_at_z() {
+        let _ = part_1_from_str("A [\n", &Params { game: Game::rock_paper_scissors_lizard_spock() });
+    }
+
+    #[test]
+    fn every_cipher_of_the_sample() {
+        let sample = std::fs::read_to_string("input/2022/sample/2.txt").unwrap();
+        let decodings = decode(&sample, &Game::rock_paper_scissors(), Some(15));
+        assert_eq!(decodings.decodings.len(), 12);
+        // three rounds don't narrow it down much
+        let matching: Vec<Cipher> = decodings.matching().map(|decoding| decoding.cipher).collect();
+        assert_eq!(matching.len(), 8);
+        assert!(matching.contains(&Cipher::Moves([0, 1, 2])) && matching.contains(&Cipher::Outcomes([Outcome::Draw, Outcome::Lose, Outcome::Win])));
+        let part_2 = decodings.decodings.iter().find(|decoding| decoding.cipher.part() == Some(2)).unwrap();
+        assert_eq!(part_2.score, Ok(12));
+
+        let report = decodings.to_string();
+        assert!(report.contains("X rock, Y paper, Z scissors  15  part 1  <- matches\n"), "{report}");
+        assert!(report.ends_with("8 of 12 readings score 15\n"), "{report}");
+        assert!(decode(&sample, &Game::rock_paper_scissors(), Some(1)).to_string().ends_with("no reading scores 1\n"));
+        assert_eq!(decode(&sample, &Game::rock_paper_scissors_lizard_spock(), None).decodings.len(), 5 * 4 * 3 + 6);
+    }
+
+    type Part = fn(&str, &Params) -> Result<u64, Unsolved>;
+
+    // Rewriting the second column so that the cipher becomes the puzzle's own reading can't change the score
+    #[test]
+    fn ciphers_are_relabellings() {
+        let game = Game::rock_paper_scissors();
+        for seed in 0..20 {
+            let input = generate(&mut Rng::new(seed), 30);
+            for decoding in decode(&input, &game, None).decodings {
+                let (letters, part): ([char; 3], Part) = match decoding.cipher {
+                    Cipher::Moves(moves) => (moves.map(|m| ['X', 'Y', 'Z'][m]), part_1_from_str),
+                    Cipher::Outcomes(outcomes) => (outcomes.map(|o| ['X', 'Y', 'Z'][o as usize]), part_2_from_str),
+                };
+                let relabelled: String = input.lines()
+                    .map(|line| format!("{} {}\n", &line[..1], letters[(line.as_bytes()[2] - b'X') as usize]))
+                    .collect();
+                assert_eq!(decoding.score, part(&relabelled, &Params::default()), "{:?} on\n{input}", decoding.cipher);
+            }
+        }
+    }
+
+    #[test]
+    fn sample_model() {
+        let sample = std::fs::read_to_string("input/2022/sample/2.txt").unwrap();
+        let model = model(&sample, &Game::rock_paper_scissors(), 100, 0).unwrap();
+        assert_eq!(model.counts, [1, 1, 1]);
+        assert_eq!(model.totals, [12, 15, 18]);
+        assert_eq!((model.best, model.guide_total), (2, 15));
+        assert_eq!(model.to_string(), super::model(&sample, &Game::rock_paper_scissors(), 100, 0).unwrap().to_string());
+        assert!(matches!(super::model("", &Game::rock_paper_scissors(), 100, 0), Err(Unsolved::NoSolution)));
+
+        // a whole guide's worth of rounds against the opponent plays every round once, wherever it starts
+        let fixed = &model.tournaments[2];
+        assert_eq!(fixed.players, [Player::Fixed(2), Player::Opponent]);
+        assert_eq!(fixed.means[0], 6.0);
+    }
+
+    #[test]
+    fn adaptive_players_catch_a_repetitive_opponent() {
+        let game = Game::rock_paper_scissors();
+        let guide = vec![(0, 1); 10];
+        let rng = &mut Rng::new(0);
+        // paper from the second round on, whatever it opened with
+        let beater = tournament(&game, &guide, [Player::LastMoveBeater, Player::Opponent], 20, 10, rng);
+        assert_eq!((beater.wins, beater.draws), ([20, 0], 0));
+        assert!(beater.means[0] >= (9.0 * 8.0 + 3.0) / 10.0);
+        let counter = tournament(&game, &guide, [Player::FrequencyCounter, Player::Opponent], 20, 10, rng);
+        assert_eq!(counter.wins, [20, 0]);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{cancel::Unsolved, generate::Rng, input::Input, params::params, trace::{debug, trace}};
+
+params! {
+    decryption_key: i64 = 811589153, 811589153;
+    mixes: usize = 10, 10;
+}
+
+fn ciphertext_from_str(input: &str) -> Vec<i64> {
+    Input::new(input)
+        .lines()
+        .map(|line| line.parse().unwrap())
+        .collect()
+}
+
+// Moving a number all the way round the others leaves it where it started, so only what's left over after whole laps
+// of the other len - 1 numbers matters, however big the number is
+fn mix(ciphertext: &[i64], mixes: usize) -> Vec<i64> {
+    let mut order_to_value_and_position: Vec<(i64, usize)> = ciphertext.iter().enumerate().map(|(i, &o)| (o, i)).collect();
+    let mut value_and_initial_position_buffer = order_to_value_and_position.clone();
+    let len = ciphertext.len() as i64;
+
+    for round in 1..=mixes {
+        for i in 0..len as usize {
+            let current_index = order_to_value_and_position[i].1;
+            let target_index = if len == 1 {
+                current_index
+            } else {
+                let target_index = current_index as i64 + order_to_value_and_position[i].0.rem_euclid(len - 1);
+                (if target_index >= len { target_index - (len - 1) } else { target_index }) as usize
+            };
+            debug_assert_eq!(
+                value_and_initial_position_buffer[current_index].0, order_to_value_and_position[i].0,
+                "number {i} should be at {current_index}",
+            );
+            let being_mixed = value_and_initial_position_buffer[current_index];
+            trace!("{} moves from {current_index} to {target_index}", being_mixed.0);
+            if current_index < target_index {
+                for j in current_index + 1..=target_index {
+                    let being_moved = value_and_initial_position_buffer[j];
+                    order_to_value_and_position[being_moved.1].1 = j - 1;
+                    value_and_initial_position_buffer[j - 1] = being_moved;
+                }
+            } else if current_index > target_index {
+                for j in (target_index..=current_index - 1).rev() {
+                    let being_moved = value_and_initial_position_buffer[j];
+                    order_to_value_and_position[being_moved.1].1 = j + 1;
+                    value_and_initial_position_buffer[j + 1] = being_moved;
+                }
+            }
+            order_to_value_and_position[being_mixed.1].1 = target_index;
+            value_and_initial_position_buffer[target_index] = being_mixed;
+        }
+        debug!("mix {round} of {mixes} done");
+    }
+
+    value_and_initial_position_buffer.into_iter().map(|(x, _)| x).collect()
+}
+
+// NoSolution without a zero to count from
+fn coordinate_sum(plaintext: &[i64]) -> Result<i64, Unsolved> {
+    let zero_index = plaintext.iter().position(|&x| x == 0).ok_or(Unsolved::NoSolution)?;
+    let index_1000 = (zero_index + 1000).rem_euclid(plaintext.len());
+    let index_2000 = (zero_index + 2000).rem_euclid(plaintext.len());
+    let index_3000 = (zero_index + 3000).rem_euclid(plaintext.len());
+
+    plaintext[index_1000].checked_add(plaintext[index_2000])
+        .and_then(|sum| sum.checked_add(plaintext[index_3000]))
+        .ok_or(Unsolved::Overflow)
+}
+
+#[allow(dead_code)]
+pub fn part_1() -> Result<i64, Unsolved> {
+    part_1_from_str(&std::fs::read_to_string("input/2022/real/20.txt").unwrap(), &Params::default())
+}
+
+// No key and a single mix
+pub fn part_1_from_str(input: &str, _params: &Params) -> Result<i64, Unsolved> {
+    let ciphertext = ciphertext_from_str(input);
+    let plaintext = mix(&ciphertext, 1);
+    coordinate_sum(&plaintext)
+}
+
+#[allow(dead_code)]
+pub fn part_2() -> Result<i64, Unsolved> {
+    part_2_from_str(&std::fs::read_to_string("input/2022/real/20.txt").unwrap(), &Params::default())
+}
+
+pub fn part_2_from_str(input: &str, params: &Params) -> Result<i64, Unsolved> {
+    let ciphertext = ciphertext_from_str(input).into_iter()
+        .map(|x| x.checked_mul(params.decryption_key).ok_or(Unsolved::Overflow))
+        .collect::<Result<Vec<_>, _>>()?;
+    let plaintext = mix(&ciphertext, params.mixes);
+    coordinate_sum(&plaintext)
+}
+
+// `size` numbers to mix, exactly one of them zero
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut numbers: Vec<i64> = (1..size.max(1))
+        .map(|_| rng.range(1..=10_000) * if rng.chance(0.5) { 1 } else { -1 })
+        .collect();
+    numbers.insert(rng.below(numbers.len() + 1), 0);
+    numbers.into_iter().map(|n| format!("{n}\n")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::differential;
+
+    // Take each number out and put it back in by hand, moving through the list with the number removed
+    fn reference(ciphertext: &[i64], mixes: usize) -> Vec<i64> {
+        let mut order: Vec<usize> = (0..ciphertext.len()).collect();
+        for _ in 0..mixes {
+            for (original, &number) in ciphertext.iter().enumerate() {
+                let from = order.iter().position(|&o| o == original).unwrap();
+                order.remove(from);
+                let to = (from as i64 + number).rem_euclid(order.len().max(1) as i64) as usize;
+                order.insert(to, original);
+            }
+        }
+        order.into_iter().map(|o| ciphertext[o]).collect()
+    }
+
+    // The list is circular, so only compare from the zero onwards
+    fn from_zero(mut plaintext: Vec<i64>) -> Vec<i64> {
+        let zero_index = plaintext.iter().position(|&x| x == 0).unwrap();
+        plaintext.rotate_left(zero_index);
+        plaintext
+    }
+
+    #[test]
+    fn mix_matches_moving_every_number_by_hand() {
+        for (key, mixes) in [(1, 1), (811589153, 10)] {
+            differential(
+                generate,
+                1..=60,
+                50,
+                |input| {
+                    let ciphertext: Vec<i64> = ciphertext_from_str(input).into_iter().map(|x| x * key).collect();
+                    from_zero(mix(&ciphertext, mixes))
+                },
+                |input| {
+                    let ciphertext: Vec<i64> = ciphertext_from_str(input).into_iter().map(|x| x * key).collect();
+                    Some(from_zero(reference(&ciphertext, mixes)))
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn numbers_past_i64_overflow() {
+        let half = i64::MAX / 2 + 1;
+        // mixes fine, but with only three numbers the coordinates are all of them, which add up to more than i64::MAX
+        assert_eq!(part_1_from_str(&format!("0\n{half}\n{half}\n"), &Params::default()), Err(Unsolved::Overflow));
+        // too big once the key's applied
+        assert_eq!(part_2_from_str(&format!("0\n{half}\n"), &Params::default()), Err(Unsolved::Overflow));
+    }
+
+    #[test]
+    fn numbers_move_by_what_is_left_after_whole_laps() {
+        assert_eq!(part_1_from_str(&format!("0\n{}\n", i64::MAX), &Params::default()), Ok(0));
+        // past whole laps of the other two, i64::MAX moves on one place and i64::MIN doesn't move at all
+        assert_eq!(part_1_from_str(&format!("0\n{}\n-1\n", i64::MAX), &Params::default()), Ok(i64::MAX - 1));
+        assert_eq!(part_1_from_str(&format!("0\n{}\n1\n", i64::MIN), &Params::default()), Ok(i64::MIN + 1));
+        assert_eq!(part_1_from_str("0\n", &Params::default()), Ok(0));
+        assert_eq!(part_2_from_str("0\n", &Params::default()), Ok(0));
+        assert_eq!(part_1_from_str("5\n", &Params::default()), Err(Unsolved::NoSolution));
+    }
+}
@@ -0,0 +1,368 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{cancel::Unsolved, generate::Rng, input::Input, trace::{debug, trace}};
+
+#[derive(Debug, Eq, PartialEq)]
+enum Op {
+    Add, Sub, Mul, Div, Eq
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum R {
+    N(i64),
+    O(String, Op, String),
+}
+
+fn riddle_from_str(input: &str) -> HashMap<String, R> {
+    Input::new(input)
+        .lines()
+        .map(|line| {
+            let (name, r) = line.split_once(": ").unwrap();
+            r.parse()
+                .map(|n| (name.to_owned(), R::N(n)))
+                .unwrap_or_else(|_| {
+                    let op = match r.chars().nth(5).unwrap() {
+                        '+' => Op::Add,
+                        '-' => Op::Sub,
+                        '*' => Op::Mul,
+                        '/' => Op::Div,
+                        _   => unreachable!(),
+                    };
+                    (name.to_owned(), R::O(r[0..4].to_owned(), op, r[7..].to_owned()))
+                })
+        }).collect()
+}
+
+// Like i64::checked_div, except that dividing by zero has no answer at all rather than one too big for an i64
+fn checked_div(a: i64, b: i64) -> Result<Option<i64>, Unsolved> {
+    if b == 0 {
+        return Err(Unsolved::NoSolution);
+    }
+    Ok(a.checked_div(b))
+}
+
+// Overflow if any monkey has to yell something that doesn't fit in an i64, NoSolution if one has to divide by zero
+fn solve(riddle: &HashMap<String, R>) -> Result<HashMap<String, i64>, Unsolved> {
+    let mut unfinished: HashSet<String> = HashSet::new();
+    let mut solved: HashMap<String, i64> = HashMap::new();
+    for (name, r) in riddle.iter() {
+        match r {
+            R::O(_, _, _) => { unfinished.insert(name.clone()); },
+            R::N(n) => { solved.insert(name.clone(), *n); },
+        }
+    }
+
+    for pass in 1.. {
+        let mut newly_finished: Vec<String> = vec![];
+        for term_1 in &unfinished {
+            let R::O(ref term_2, ref op, ref term_3) = riddle[term_1] else { unreachable!() };
+
+            match (solved.get(term_1), solved.get(term_2), solved.get(term_3)) {
+                (None, Some(n_2), Some(n_3)) => {
+                    let n_1 = match op {
+                        Op::Add => n_2.checked_add(*n_3),
+                        Op::Sub => n_2.checked_sub(*n_3),
+                        Op::Mul => n_2.checked_mul(*n_3),
+                        Op::Div => checked_div(*n_2, *n_3)?,
+                        Op::Eq => unreachable!(),
+                    }.ok_or(Unsolved::Overflow)?;
+                    trace!("{term_1} = {term_2} {op:?} {term_3} = {n_1}");
+                    solved.insert(term_1.clone(), n_1);
+                    newly_finished.push(term_1.clone());
+                },
+                (Some(n_1), Some(n_2), None) => {
+                    let n_3 = match op {
+                        Op::Add => n_1.checked_sub(*n_2),
+                        Op::Sub => n_2.checked_sub(*n_1),
+                        Op::Mul => checked_div(*n_1, *n_2)?,
+                        Op::Div => checked_div(*n_2, *n_1)?,
+                        Op::Eq => unreachable!(),
+                    }.ok_or(Unsolved::Overflow)?;
+                    trace!("{term_3} = {n_3} so that {term_1} = {term_2} {op:?} {term_3}");
+                    solved.insert(term_3.clone(), n_3);
+                    newly_finished.push(term_1.clone());
+                },
+                (Some(n_1), None, Some(n_3)) => {
+                    let n_2 = match op {
+                        Op::Add => n_1.checked_sub(*n_3),
+                        Op::Sub => n_1.checked_add(*n_3),
+                        Op::Mul => checked_div(*n_1, *n_3)?,
+                        Op::Div => n_1.checked_mul(*n_3),
+                        Op::Eq => unreachable!(),
+                    }.ok_or(Unsolved::Overflow)?;
+                    trace!("{term_2} = {n_2} so that {term_1} = {term_2} {op:?} {term_3}");
+                    solved.insert(term_2.clone(), n_2);
+                    newly_finished.push(term_1.clone());
+                }
+                (None, None, Some(n_3)) if *op == Op::Eq => {
+                    trace!("{term_2} = {n_3} to match {term_3}");
+                    solved.insert(term_2.clone(), *n_3);
+                    newly_finished.push(term_1.clone());
+                },
+                (None, Some(n_2), None) if *op == Op::Eq => {
+                    trace!("{term_3} = {n_2} to match {term_2}");
+                    solved.insert(term_3.clone(), *n_2);
+                    newly_finished.push(term_1.clone());
+                },
+                _ => (),
+            }
+        }
+        if newly_finished.is_empty() {
+            debug!("nothing more to propagate after {pass} passes, {} monkeys still unsolved", unfinished.len());
+            break;
+        }
+        debug!("pass {pass} settled {} monkeys", newly_finished.len());
+        newly_finished.into_iter().for_each(|name| { unfinished.remove(&name); });
+    }
+
+    Ok(solved)
+}
+
+#[allow(dead_code)]
+pub fn part_1() -> Result<i64, Unsolved> {
+    part_1_from_str(&std::fs::read_to_string("input/2022/real/21.txt").unwrap())
+}
+
+pub fn part_1_from_str(input: &str) -> Result<i64, Unsolved> {
+    let riddle = riddle_from_str(input);
+    let answers = solve(&riddle)?;
+    Ok(answers["root"])
+}
+
+#[allow(dead_code)]
+pub fn part_2() -> Result<i64, Unsolved> {
+    part_2_from_str(&std::fs::read_to_string("input/2022/real/21.txt").unwrap())
+}
+
+pub fn part_2_from_str(input: &str) -> Result<i64, Unsolved> {
+    let mut riddle = riddle_from_str(input);
+    let R::O(ref term_2, _, ref term_3) = riddle["root"] else { unreachable!(); };
+    riddle.insert("root".to_owned(), R::O(term_2.clone(), Op::Eq, term_3.clone()));
+    riddle.remove("humn");
+    let answers = solve(&riddle)?;
+    Ok(answers["humn"])
+}
+
+struct RiddleWriter<'a> {
+    rng: &'a mut Rng,
+    names: HashSet<String>,
+    lines: Vec<String>,
+}
+
+impl RiddleWriter<'_> {
+    fn name(&mut self) -> String {
+        loop {
+            let name = self.rng.lowercase(4);
+            if name != "root" && name != "humn" && self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    // A monkey that yells `value`, with about `budget` monkeys behind it. Every operation works out exactly
+    fn yelling(&mut self, value: i64, budget: usize) -> String {
+        let name = self.name();
+        if budget < 3 {
+            self.lines.push(format!("{name}: {value}"));
+            return name;
+        }
+        let left_budget = 1 + self.rng.below(budget - 2);
+        let right_budget = budget - 1 - left_budget;
+
+        let divisors: Vec<i64> = (2..=9).filter(|d| value != 0 && value % d == 0).collect();
+        let (left, op, right) = match self.rng.below(4) {
+            1 => {
+                let b = self.rng.range(1..=1000);
+                (value + b, '-', b)
+            },
+            2 if !divisors.is_empty() => {
+                let d = *self.rng.choose(&divisors);
+                (value / d, '*', d)
+            },
+            3 if value.abs() <= 1_000_000 => {
+                let d = self.rng.range(1..=9);
+                (value * d, '/', d)
+            },
+            _ => {
+                let a = self.rng.range(0..=value.max(0));
+                (a, '+', value - a)
+            },
+        };
+        let left = self.yelling(left, left_budget);
+        let right = self.yelling(right, right_budget);
+        self.lines.push(format!("{name}: {left} {op} {right}"));
+        name
+    }
+
+    // A monkey somewhere above humn, who has to yell `target` once humn gets it right in part 2.
+    // Only operations that part 2 can undo exactly go on this path
+    fn leading_to_humn(&mut self, target: i64, budget: usize) -> String {
+        if budget < 3 {
+            let part_1_shout = self.rng.range(1..=1000);
+            self.lines.push(format!("humn: {part_1_shout}"));
+            return "humn".to_owned();
+        }
+        let name = self.name();
+        let constant_budget = 1 + self.rng.below((budget - 1) / 2);
+        let humn_budget = budget - 1 - constant_budget;
+
+        let divisors: Vec<i64> = (2..=9).filter(|d| target != 0 && target % d == 0).collect();
+        let mut humn_on_left = self.rng.chance(0.5);
+        let (humn_target, op, constant) = match self.rng.below(4) {
+            1 if humn_on_left => {
+                let c = self.rng.range(1..=1000);
+                (target + c, '-', c)
+            },
+            1 => {
+                let c = target.max(0) + self.rng.range(1..=1000);
+                (c - target, '-', c)
+            },
+            2 if !divisors.is_empty() => {
+                let d = *self.rng.choose(&divisors);
+                (target / d, '*', d)
+            },
+            3 if target.abs() <= 1_000_000 => {
+                humn_on_left = true;
+                let d = self.rng.range(1..=9);
+                (target * d, '/', d)
+            },
+            _ => {
+                let c = self.rng.range(0..=target.max(0));
+                (target - c, '+', c)
+            },
+        };
+        let constant = self.yelling(constant, constant_budget);
+        let humn_side = self.leading_to_humn(humn_target, humn_budget);
+        if humn_on_left {
+            self.lines.push(format!("{name}: {humn_side} {op} {constant}"));
+        } else {
+            self.lines.push(format!("{name}: {constant} {op} {humn_side}"));
+        }
+        name
+    }
+}
+
+// About `size` monkeys, with humn buried somewhere on one side of root and a single right answer for part 2
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut writer = RiddleWriter { rng, names: HashSet::new(), lines: vec![] };
+    let budget = size.max(5) - 1;
+    let target = writer.rng.range(1..=1_000_000);
+    let humn_side = writer.leading_to_humn(target, budget / 2);
+    let other_side = writer.yelling(target, budget - budget / 2);
+    if writer.rng.chance(0.5) {
+        writer.lines.push(format!("root: {humn_side} + {other_side}"));
+    } else {
+        writer.lines.push(format!("root: {other_side} + {humn_side}"));
+    }
+
+    let RiddleWriter { rng, mut lines, .. } = writer;
+    rng.shuffle(&mut lines);
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::differential;
+
+    fn evaluate(riddle: &HashMap<String, R>, name: &str) -> i64 {
+        match &riddle[name] {
+            R::N(n) => *n,
+            R::O(left, op, right) => {
+                let (left, right) = (evaluate(riddle, left), evaluate(riddle, right));
+                match op {
+                    Op::Add => left + right,
+                    Op::Sub => left - right,
+                    Op::Mul => left * right,
+                    Op::Div => left / right,
+                    Op::Eq  => unreachable!(),
+                }
+            }
+        }
+    }
+
+    fn gcd(a: i128, b: i128) -> i128 {
+        if b == 0 { a.abs() } else { gcd(b, a % b) }
+    }
+
+    // An exact fraction, always in lowest terms with a positive denominator
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    struct Q(i128, i128);
+
+    impl Q {
+        fn new(n: i128, d: i128) -> Q {
+            let g = gcd(n, d).max(1) * d.signum();
+            Q(n / g, d / g)
+        }
+        fn add(self, o: Q) -> Q { Q::new(self.0 * o.1 + o.0 * self.1, self.1 * o.1) }
+        fn sub(self, o: Q) -> Q { Q::new(self.0 * o.1 - o.0 * self.1, self.1 * o.1) }
+        fn mul(self, o: Q) -> Q { Q::new(self.0 * o.0, self.1 * o.1) }
+        fn div(self, o: Q) -> Q { Q::new(self.0 * o.1, self.1 * o.0) }
+    }
+
+    // What a monkey yells as a linear function a * humn + b, or None if humn ends up multiplied by itself or dividing
+    fn linear(riddle: &HashMap<String, R>, name: &str) -> Option<(Q, Q)> {
+        if name == "humn" {
+            return Some((Q(1, 1), Q(0, 1)));
+        }
+        match &riddle[name] {
+            R::N(n) => Some((Q(0, 1), Q(*n as i128, 1))),
+            R::O(left, op, right) => {
+                let ((la, lb), (ra, rb)) = (linear(riddle, left)?, linear(riddle, right)?);
+                let zero = Q(0, 1);
+                match op {
+                    Op::Add => Some((la.add(ra), lb.add(rb))),
+                    Op::Sub => Some((la.sub(ra), lb.sub(rb))),
+                    Op::Mul if la == zero => Some((ra.mul(lb), rb.mul(lb))),
+                    Op::Mul if ra == zero => Some((la.mul(rb), lb.mul(rb))),
+                    Op::Div if ra == zero && rb != zero => Some((la.div(rb), lb.div(rb))),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    // Solve root's two sides as one linear equation in humn
+    fn reference_part_2(riddle: &HashMap<String, R>) -> Option<i64> {
+        let R::O(left, _, right) = &riddle["root"] else { unreachable!(); };
+        let ((la, lb), (ra, rb)) = (linear(riddle, left)?, linear(riddle, right)?);
+        let humn = rb.sub(lb).div(la.sub(ra));
+        if humn.1 == 1 { Some(humn.0 as i64) } else { None }
+    }
+
+    #[test]
+    fn part_1_matches_evaluating_recursively() {
+        differential(generate, 5..=80, 100, part_1_from_str, |input| Some(Ok(evaluate(&riddle_from_str(input), "root"))));
+    }
+
+    #[test]
+    fn part_2_matches_solving_a_linear_equation() {
+        differential(generate, 5..=80, 100, part_2_from_str, |input| reference_part_2(&riddle_from_str(input)).map(Ok));
+    }
+
+    #[test]
+    fn yells_past_i64_overflow() {
+        let input = "root: aaaa * bbbb\naaaa: 5000000000\nbbbb: 5000000000\nhumn: 1\n";
+        assert_eq!(part_1_from_str(input), Err(Unsolved::Overflow));
+
+        // fine going forwards, but undoing the division for part 2 needs 4 * 2^62
+        let input = "root: aaaa + bbbb\naaaa: humn / cccc\ncccc: 4611686018427387904\nbbbb: 4\nhumn: 1\n";
+        assert_eq!(part_1_from_str(input), Ok(4));
+        assert_eq!(part_2_from_str(input), Err(Unsolved::Overflow));
+
+        // the one division that really is too big for an i64
+        let input = format!("root: aaaa / bbbb\naaaa: {}\nbbbb: -1\nhumn: 1\n", i64::MIN);
+        assert_eq!(part_1_from_str(&input), Err(Unsolved::Overflow));
+    }
+
+    #[test]
+    fn dividing_by_zero_has_no_solution() {
+        let input = "root: aaaa / bbbb\naaaa: 5\nbbbb: humn - cccc\ncccc: 1\nhumn: 1\n";
+        assert_eq!(part_1_from_str(input), Err(Unsolved::NoSolution));
+
+        // anything times zero is zero, so there's no working humn out from what it has to be multiplied by
+        let input = "root: aaaa + bbbb\naaaa: humn * cccc\ncccc: 0\nbbbb: 0\nhumn: 1\n";
+        assert_eq!(part_1_from_str(input), Ok(0));
+        assert_eq!(part_2_from_str(input), Err(Unsolved::NoSolution));
+    }
+}
@@ -1,5 +1,11 @@
 use std::collections::{HashSet, HashMap};
 
+use crate::{cancel::{self, Unsolved}, generate::Rng, input::Input, params::params, progress, render::{Colour, Frame}};
+
+params! {
+    rounds: usize = 10, 10;
+}
+
 type Position = (i32, i32);
 
 #[derive(Debug, Clone, Copy)]
@@ -38,16 +44,14 @@ impl Direction {
 }
 
 fn positions_from_file(path: &str) -> HashSet<Position> {
+    positions_from_str(&std::fs::read_to_string(path).unwrap())
+}
+
+fn positions_from_str(input: &str) -> HashSet<Position> {
     let mut positions = HashSet::new();
-    std::fs::read_to_string(path).unwrap()
-        .lines().enumerate()
-        .for_each(|(y, line)|
-            line.chars().enumerate().for_each(|(x, c)| 
-                if c == '#' {
-                    positions.insert((x as i32, y as i32));
-                } 
-            )
-        );
+    Input::new(input).grid()
+        .filter(|&(_, _, c)| c == '#')
+        .for_each(|(x, y, _)| { positions.insert((x as i32, y as i32)); });
     positions
 }
 
@@ -104,32 +108,99 @@ fn round(positions: &HashSet<Position>, primary_direction: Direction) -> HashSet
     new_positions
 }
 
-fn area(positions: &HashSet<Position>) -> i32 {
+// In i64, since a grove only needs to be a few hundred thousand characters across for the area to outgrow an i32
+fn area(positions: &HashSet<Position>) -> i64 {
     let min_x = positions.iter().map(|&(x, _)| x).min().unwrap();
     let max_x = positions.iter().map(|&(x, _)| x).max().unwrap();
     let min_y = positions.iter().map(|&(_, y)| y).min().unwrap();
     let max_y = positions.iter().map(|&(_, y)| y).max().unwrap();
-    (max_x - min_x + 1) * (max_y - min_y + 1)
+    (i64::from(max_x) - i64::from(min_x) + 1) * (i64::from(max_y) - i64::from(min_y) + 1)
 }
 
 #[allow(dead_code)]
-pub fn part_1() -> i32 {
-    let mut positions = positions_from_file("input/real/23.txt");
-    for direction in Direction::N.iter().take(10) {
+pub fn part_1() -> i64 {
+    part_1_from_str(&std::fs::read_to_string("input/2022/real/23.txt").unwrap(), &Params::default())
+}
+
+pub fn part_1_from_str(input: &str, params: &Params) -> i64 {
+    let mut positions = positions_from_str(input);
+    for direction in Direction::N.iter().take(params.rounds) {
         positions = round(&positions, direction);
     }
-    area(&positions) - positions.len() as i32
+    area(&positions) - positions.len() as i64
 }
 
 #[allow(dead_code)]
-pub fn part_2() -> usize {
-    let mut positions = positions_from_file("input/real/23.txt");
+pub fn part_2() -> Result<usize, Unsolved> {
+    part_2_from_str(&std::fs::read_to_string("input/2022/real/23.txt").unwrap(), &Params::default())
+}
+
+// Runs until the elves settle, however many rounds that takes
+pub fn part_2_from_str(input: &str, _params: &Params) -> Result<usize, Unsolved> {
+    let mut positions = positions_from_str(input);
+    let _progress = progress::begin("rounds until the elves settle", None);
     for (i, direction) in Direction::N.iter().enumerate() {
+        cancel::check()?;
+        progress::advance(1);
         let new_positions = round(&positions, direction);
         if new_positions == positions {
-            return i + 1;
+            return Ok(i + 1);
         }
         positions = new_positions;
     }
     unreachable!();
 }
+
+const ELF: u8 = 1;
+
+pub const PALETTE: [Colour; 2] = [
+    [25, 60, 30],   // ground
+    [240, 240, 220], // elf
+];
+
+// The elves spreading out over the first `count` rounds, or until they stop moving
+pub fn frames(path: &str, count: usize) -> Vec<Frame> {
+    let mut positions = positions_from_file(path);
+    let mut rounds = vec![positions.clone()];
+    for direction in Direction::N.iter().take(count) {
+        let new_positions = round(&positions, direction);
+        if new_positions == positions {
+            break;
+        }
+        positions = new_positions;
+        rounds.push(positions.clone());
+    }
+
+    let all = || rounds.iter().flatten();
+    let min_x = all().map(|&(x, _)| x).min().unwrap();
+    let max_x = all().map(|&(x, _)| x).max().unwrap();
+    let min_y = all().map(|&(_, y)| y).min().unwrap();
+    let max_y = all().map(|&(_, y)| y).max().unwrap();
+
+    rounds.iter()
+        .map(|positions| {
+            let mut frame = Frame::new((max_x - min_x + 1) as usize, (max_y - min_y + 1) as usize);
+            positions.iter().for_each(|&(x, y)| frame.set((x - min_x) as usize, (y - min_y) as usize, ELF));
+            frame
+        }).collect()
+}
+
+// A `size` by `size` grove with elves on about half of it
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| (0..size).map(|_| if rng.chance(0.5) { '#' } else { '.' }).collect::<String>() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Two lonely elves at opposite corners, who never move, around more ground than an i32 can count
+    #[test]
+    fn area_past_i32_is_counted() {
+        let side = 46_342;
+        let input = format!("#\n{}{}#\n", "\n".repeat(side - 2), ".".repeat(side - 1));
+        assert_eq!(part_1_from_str(&input, &Params::default()), 46_342 * 46_342 - 2);
+    }
+}
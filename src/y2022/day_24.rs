@@ -0,0 +1,287 @@
+use std::collections::HashSet;
+
+use crate::{cancel::{self, Unsolved}, generate::Rng, input::Input, progress, render::{Colour, Frame}, trace::{debug, trace}};
+
+#[derive(Clone, Copy)]
+enum Direction { N, S, E, W, }
+type Position = (i32, i32);
+type Blizzard = (Position, Direction);
+
+// blizzards, start, end, wall corners
+fn valley_from_file(path: &str) -> (Vec<Blizzard>, Position, Position, (Position, Position)) {
+    valley_from_str(&std::fs::read_to_string(path).unwrap())
+}
+
+fn valley_from_str(content: &str) -> (Vec<Blizzard>, Position, Position, (Position, Position)) {
+    let content = Input::new(content);
+    let lines: Vec<&str> = content.lines().collect();
+
+    let blizzards = content.grid()
+        .filter_map(|(x, y, c)| {
+            let direction = match c {
+                '^' => Direction::N,
+                'v' => Direction::S,
+                '>' => Direction::E,
+                '<' => Direction::W,
+                _   => return None,
+            };
+            Some(((x as i32, y as i32), direction))
+        }).collect();
+
+    let start_x = lines[0].chars().position(|c| c == '.').unwrap() as i32;
+    let end_x = lines.last().unwrap().chars().position(|c| c == '.').unwrap() as i32;
+
+    (blizzards, (start_x, 0), (end_x, lines.len() as i32 - 1), ((0, 0), (lines[0].len() as i32 - 1, lines.len() as i32 - 1)))
+}
+
+fn tick_blizzards(blizzards: &[Blizzard], bounds: (Position, Position)) -> (Vec<Blizzard>, HashSet<Position>) {
+    let mut new_blizzards = Vec::with_capacity(blizzards.len());
+    let mut occupied = HashSet::new();
+
+    for &(mut position, direction) in blizzards {
+        let (dx, dy) = match direction {
+            Direction::N => (0, -1),
+            Direction::S => (0, 1),
+            Direction::E => (1, 0),
+            Direction::W => (-1, 0),
+        };
+        position.0 += dx;
+        position.1 += dy;
+        if position.0 <= bounds.0.0 {
+            position.0 += bounds.1.0 - bounds.0.0 - 1;
+        } else if position.0 >= bounds.1.0 {
+            position.0 -= bounds.1.0 - bounds.0.0 - 1;
+        } else if position.1 <= bounds.0.1 {
+            position.1 += bounds.1.1 - bounds.0.1 - 1;
+        } else if position.1 >= bounds.1.1 {
+            position.1 -= bounds.1.1 - bounds.0.1 - 1;
+        }
+
+        new_blizzards.push((position, direction));
+        occupied.insert(position);
+    }
+
+    (new_blizzards, occupied)
+}
+
+// Everywhere we could be after one more minute, or None if that includes the end
+fn spread(possible_positions: &HashSet<Position>, occupied: &HashSet<Position>, start: Position, end: Position, bounds: (Position, Position)) -> Option<HashSet<Position>> {
+    let mut new_positions = HashSet::new();
+
+    for position in possible_positions {
+        for (dx, dy) in &[(0, 0), (0, -1), (0, 1), (-1, 0), (1, 0)] {
+            let possible = (position.0 + dx, position.1 + dy);
+            if possible == end {
+                return None;
+            } else if possible != start && (possible.0 <= bounds.0.0 || possible.0 >= bounds.1.0 || possible.1 <= bounds.0.1 || possible.1 >= bounds.1.1) {
+                continue;
+            } else if !occupied.contains(&possible) {
+                new_positions.insert(possible);
+            }
+        }
+    }
+
+    Some(new_positions)
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+// Waiting at the start is always safe, so everywhere we could be only grows from one lap of the blizzards to the next.
+// If a whole lap adds nothing new, the end is out of reach
+fn solve(mut blizzards: Vec<Blizzard>, start: Position, end: Position, bounds: (Position, Position)) -> Result<(i32, Vec<Blizzard>), Unsolved> {
+    let (width, height) = ((bounds.1.0 - bounds.0.0 - 1).max(1), (bounds.1.1 - bounds.0.1 - 1).max(1));
+    let lap = width * height / gcd(width, height);
+
+    let mut possible_positions: HashSet<Position> = HashSet::new();
+    possible_positions.insert(start);
+    let mut last_lap = HashSet::new();
+
+    let _progress = progress::begin("minutes through the blizzards", None);
+    for i in 1.. {
+        cancel::check()?;
+        progress::advance(1);
+        let (new_blizzards, occupied) = tick_blizzards(&blizzards, bounds);
+        let Some(new_positions) = spread(&possible_positions, &occupied, start, end, bounds) else {
+            debug!("reached {end:?} from {start:?} after {i} minutes");
+            return Ok((i, new_blizzards));
+        };
+        trace!("minute {i}: {} possible positions", new_positions.len());
+        progress::explored(new_positions.len() as u64);
+
+        blizzards = new_blizzards;
+        possible_positions = new_positions;
+        if i % lap == 0 {
+            if possible_positions == last_lap {
+                debug!("{end:?} is out of reach from {start:?}, nothing new after {i} minutes");
+                return Err(Unsolved::NoSolution);
+            }
+            last_lap = possible_positions.clone();
+        }
+    }
+
+    unreachable!();
+}
+
+#[allow(dead_code)]
+pub fn part_1() -> Result<i32, Unsolved> {
+    part_1_from_str(&std::fs::read_to_string("input/2022/real/24.txt").unwrap())
+}
+
+pub fn part_1_from_str(input: &str) -> Result<i32, Unsolved> {
+    let (blizzards, start, end, bounds) = valley_from_str(input);
+
+    Ok(solve(blizzards, start, end, bounds)?.0)
+}
+
+#[allow(dead_code)]
+pub fn part_2() -> Result<i32, Unsolved> {
+    part_2_from_str(&std::fs::read_to_string("input/2022/real/24.txt").unwrap())
+}
+
+pub fn part_2_from_str(input: &str) -> Result<i32, Unsolved> {
+    let (blizzards, start, end, bounds) = valley_from_str(input);
+
+    let (time_1, blizzards) = solve(blizzards, start, end, bounds)?;
+    let (time_2, blizzards) = solve(blizzards, end, start, bounds)?;
+    let (time_3, _        ) = solve(blizzards, start, end, bounds)?;
+
+    Ok(time_1 + time_2 + time_3)
+}
+
+const WALL: u8 = 1;
+const BLIZZARD: u8 = 2;
+const EXPEDITION: u8 = 3;
+
+pub const PALETTE: [Colour; 4] = [
+    [30, 30, 40],    // clear ground
+    [110, 110, 120], // wall
+    [150, 200, 255], // blizzard
+    [255, 120, 40],  // somewhere the expedition could be
+];
+
+// Minute by minute through the first trip across the valley, showing every place we could have reached
+pub fn frames(path: &str, count: usize) -> Vec<Frame> {
+    let (mut blizzards, start, end, bounds) = valley_from_file(path);
+    let (width, height) = (bounds.1.0 as usize + 1, bounds.1.1 as usize + 1);
+
+    let draw = |blizzards: &[Blizzard], possible_positions: &HashSet<Position>| {
+        let mut frame = Frame::new(width, height);
+        for x in 0..width {
+            frame.set(x, 0, WALL);
+            frame.set(x, height - 1, WALL);
+        }
+        for y in 0..height {
+            frame.set(0, y, WALL);
+            frame.set(width - 1, y, WALL);
+        }
+        frame.set(start.0 as usize, start.1 as usize, 0);
+        frame.set(end.0 as usize, end.1 as usize, 0);
+        blizzards.iter().for_each(|&((x, y), _)| frame.set(x as usize, y as usize, BLIZZARD));
+        possible_positions.iter().for_each(|&(x, y)| frame.set(x as usize, y as usize, EXPEDITION));
+        frame
+    };
+
+    let mut possible_positions = HashSet::from([start]);
+    let mut frames = vec![draw(&blizzards, &possible_positions)];
+    while frames.len() < count {
+        let (new_blizzards, occupied) = tick_blizzards(&blizzards, bounds);
+        let Some(new_positions) = spread(&possible_positions, &occupied, start, end, bounds) else {
+            frames.push(draw(&new_blizzards, &HashSet::from([end])));
+            break;
+        };
+        blizzards = new_blizzards;
+        possible_positions = new_positions;
+        frames.push(draw(&blizzards, &possible_positions));
+    }
+    frames
+}
+
+// A valley `size` wide and a third as tall between the walls, with blizzards on about a third of the ground.
+// Like the real thing, nothing blows up or down the entrance and exit columns
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(3);
+    let height = (size / 3).max(2);
+    let mut lines = vec![format!("#.{}", "#".repeat(width))];
+    for _ in 0..height {
+        let ground: String = (1..=width)
+            .map(|x| {
+                if !rng.chance(0.3) {
+                    '.'
+                } else if x == 1 || x == width {
+                    *rng.choose(&['<', '>'])
+                } else {
+                    *rng.choose(&['^', 'v', '<', '>'])
+                }
+            }).collect();
+        lines.push(format!("#{ground}#"));
+    }
+    lines.push(format!("{}.#", "#".repeat(width)));
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+    use crate::check::differential;
+
+    // Breadth-first search over (position, minute), working out where every blizzard is straight from where it
+    // started. The blizzards repeat every lcm(width, height) minutes, which keeps the search finite
+    fn reference(input: &str) -> Option<(i32, i32)> {
+        let (blizzards, start, end, bounds) = valley_from_str(input);
+        let (width, height) = (bounds.1.0 - 1, bounds.1.1 - 1);
+        let period = width * height / gcd(width, height);
+        let occupied: Vec<HashSet<Position>> = (0..period)
+            .map(|minute| blizzards.iter()
+                .map(|&((x, y), direction)| match direction {
+                    Direction::N => (x, (y - 1 - minute).rem_euclid(height) + 1),
+                    Direction::S => (x, (y - 1 + minute).rem_euclid(height) + 1),
+                    Direction::W => ((x - 1 - minute).rem_euclid(width) + 1, y),
+                    Direction::E => ((x - 1 + minute).rem_euclid(width) + 1, y),
+                }).collect())
+            .collect();
+        let in_valley = |(x, y): Position| x >= 1 && x <= width && y >= 1 && y <= height;
+
+        let trip = |from: Position, to: Position, departure: i32| -> Option<i32> {
+            let mut seen = HashSet::from([(from, departure % period)]);
+            let mut queue = VecDeque::from([(from, departure)]);
+            while let Some((position, minute)) = queue.pop_front() {
+                for (dx, dy) in [(0, 0), (0, -1), (0, 1), (-1, 0), (1, 0)] {
+                    let next = (position.0 + dx, position.1 + dy);
+                    if next == to {
+                        return Some(minute + 1);
+                    }
+                    let free = next == from || (in_valley(next) && !occupied[((minute + 1) % period) as usize].contains(&next));
+                    if free && seen.insert((next, (minute + 1) % period)) {
+                        queue.push_back((next, minute + 1));
+                    }
+                }
+            }
+            None
+        };
+
+        let there = trip(start, end, 0)?;
+        let back = trip(end, start, there)?;
+        Some((there, trip(start, end, back)?))
+    }
+
+    #[test]
+    fn solve_matches_searching_positions_and_minutes() {
+        differential(
+            generate,
+            3..=15,
+            50,
+            |input| part_1_from_str(input).ok().zip(part_2_from_str(input).ok()),
+            |input| Some(reference(input)),
+        );
+    }
+
+    #[test]
+    fn a_sealed_valley_has_no_solution() {
+        // the only cell is a blizzard that wraps onto itself every minute
+        assert_eq!(part_1_from_str("#.#\n#>#\n#.#\n"), Err(Unsolved::NoSolution));
+    }
+}
@@ -0,0 +1,340 @@
+use std::{collections::HashMap, fmt, fs, str::FromStr};
+
+use crate::{generate::Rng, input::Input, params::params};
+
+params! {
+    group_size: usize = 3, 3;
+    shared: Shared = Shared::One, Shared::One;
+    priorities: PriorityTable = PriorityTable::letters(), PriorityTable::letters();
+}
+
+// What the parts add up of the items a rucksack's halves (or a group's rucksacks) have in common: the one item the
+// puzzle promises, turning down anything else, or every item they share
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shared {
+    One,
+    All,
+}
+
+impl FromStr for Shared {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Shared, String> {
+        match s {
+            "one" => Ok(Shared::One),
+            "all" => Ok(Shared::All),
+            _ => Err(format!("expected one or all, not {s}")),
+        }
+    }
+}
+
+// Which characters are items and what each is worth. An item's place in the table is its bit in an ItemSet
+#[derive(Clone)]
+pub struct PriorityTable {
+    items: Vec<(char, u64)>,
+    places: HashMap<char, usize>,
+}
+
+impl PriorityTable {
+    fn add(&mut self, item: char, priority: u64) -> Result<(), String> {
+        if item.is_whitespace() || item.is_control() {
+            return Err(format!("{item:?} can't be an item"));
+        }
+        if self.places.insert(item, self.items.len()).is_some() {
+            return Err(format!("{item:?} is in the table twice"));
+        }
+        self.items.push((item, priority));
+        Ok(())
+    }
+
+    // The puzzle's: a-z are 1 to 26 and A-Z 27 to 52
+    pub fn letters() -> PriorityTable {
+        PriorityTable::from_table("a-z 1\nA-Z 27").unwrap()
+    }
+
+    // An item, or a range of them, to a line with the priority of the first; a range counts up from there. Any Unicode
+    // scalar can be an item apart from whitespace and control characters:
+    //   # comments and blank lines are skipped
+    //   a-z 1
+    //   é 100
+    pub fn from_table(table: &str) -> Result<PriorityTable, String> {
+        let mut priorities = PriorityTable { items: vec![], places: HashMap::new() };
+        for (i, line) in Input::new(table).lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let reject = |e: String| format!("line {}: {e}", i + 1);
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [items, priority] = fields.as_slice() else {
+                return Err(reject(format!("expected an item or a range and a priority: {line}")));
+            };
+            let priority: u64 = priority.parse().map_err(|_| reject(format!("not a priority: {priority}")))?;
+            let items: Vec<char> = match *items.chars().collect::<Vec<_>>().as_slice() {
+                [item] => vec![item],
+                [first, '-', last] if first <= last => (first..=last).collect(),
+                _ => return Err(reject(format!("expected an item or a range like a-z: {items}"))),
+            };
+            for (item, offset) in items.into_iter().zip(0..) {
+                let priority = priority.checked_add(offset).ok_or_else(|| reject(format!("{item:?} would be past {}", u64::MAX)))?;
+                priorities.add(item, priority).map_err(reject)?;
+            }
+        }
+        Ok(priorities)
+    }
+
+    pub fn size(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn place(&self, item: char) -> Option<usize> {
+        self.places.get(&item).copied()
+    }
+
+    pub fn item(&self, place: usize) -> char {
+        self.items[place].0
+    }
+
+    pub fn priority(&self, place: usize) -> u64 {
+        self.items[place].1
+    }
+}
+
+// Just the size, as the whole table would drown out the rest of the parameters
+impl fmt::Debug for PriorityTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PriorityTable({} items)", self.size())
+    }
+}
+
+// "letters" for the puzzle's table, or the path of one
+impl FromStr for PriorityTable {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<PriorityTable, String> {
+        match s {
+            "letters" => Ok(PriorityTable::letters()),
+            path => PriorityTable::from_table(&fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?)
+                .map_err(|e| format!("{path}: {e}")),
+        }
+    }
+}
+
+// Why the parts turned the input down, by the line of the rucksack (or the first of the group) that was wrong
+#[derive(Debug, PartialEq, Eq)]
+pub enum Rejection {
+    NotAnItem { line: usize, item: char },
+    NothingShared { line: usize },
+    SharedSeveral { line: usize, items: Vec<char> },
+    Short { line: usize, elves: usize },
+    Overflow,
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rejection::NotAnItem { line, item } => write!(f, "line {line}: {item:?} isn't an item"),
+            Rejection::NothingShared { line } => write!(f, "line {line}: nothing in common"),
+            Rejection::SharedSeveral { line, items } => write!(f, "line {line}: {} in common", items.iter().collect::<String>()),
+            Rejection::Short { line, elves } => write!(f, "line {line}: a group of only {elves}"),
+            Rejection::Overflow => write!(f, "overflowed"),
+        }
+    }
+}
+
+// Each rucksack as its items' places in the table
+fn rucksacks_from_str(input: &str, priorities: &PriorityTable) -> Result<Vec<Vec<usize>>, Rejection> {
+    Input::new(input).lines().enumerate()
+        .map(|(i, line)| line.chars()
+            .map(|item| priorities.place(item).ok_or(Rejection::NotAnItem { line: i + 1, item }))
+            .collect())
+        .collect()
+}
+
+// Items as bits, each at its place in the priority table, so the puzzle's 52 fit in a single u64
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ItemSet(Vec<u64>);
+
+impl ItemSet {
+    pub fn from_places(places: &[usize], priorities: &PriorityTable) -> ItemSet {
+        let mut words = vec![0; priorities.size().div_ceil(64)];
+        for &place in places {
+            words[place / 64] |= 1 << (place % 64);
+        }
+        ItemSet(words)
+    }
+
+    // What every set has
+    pub fn intersection(sets: impl IntoIterator<Item = ItemSet>) -> ItemSet {
+        sets.into_iter()
+            .reduce(|a, b| ItemSet(a.0.iter().zip(&b.0).map(|(a, b)| a & b).collect()))
+            .unwrap()
+    }
+
+    pub fn count(&self) -> u32 {
+        self.0.iter().map(|word| word.count_ones()).sum()
+    }
+
+    // In table order
+    pub fn places(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.iter().enumerate()
+            .flat_map(|(i, &word)| (0..64).filter(move |bit| word & 1 << bit != 0).map(move |bit| i * 64 + bit))
+    }
+}
+
+// The sum of the priorities of what each rucksack or group (given with its line) shares
+fn total(groups: impl Iterator<Item = (usize, ItemSet)>, params: &Params) -> Result<u64, Rejection> {
+    let priorities = &params.priorities;
+    groups
+        .map(|(line, set)| match (params.shared, set.count()) {
+            (Shared::All, _) | (Shared::One, 1) => Ok(set.places().map(|place| priorities.priority(place)).collect::<Vec<_>>()),
+            (Shared::One, 0) => Err(Rejection::NothingShared { line }),
+            (Shared::One, _) => Err(Rejection::SharedSeveral { line, items: set.places().map(|place| priorities.item(place)).collect() }),
+        })
+        .try_fold(0, |total: u64, priorities| priorities?.into_iter().try_fold(total, u64::checked_add).ok_or(Rejection::Overflow))
+}
+
+#[allow(dead_code)]
+pub fn part_1() -> Result<u64, Rejection> {
+    part_1_from_str(&std::fs::read_to_string("input/2022/real/3.txt").unwrap(), &Params::default())
+}
+
+pub fn part_1_from_str(input: &str, params: &Params) -> Result<u64, Rejection> {
+    let priorities = &params.priorities;
+    let compartments = rucksacks_from_str(input, priorities)?.into_iter().enumerate().map(|(i, rucksack)| {
+        let (first_half, second_half) = rucksack.split_at(rucksack.len() / 2);
+        let halves = [ItemSet::from_places(first_half, priorities), ItemSet::from_places(second_half, priorities)];
+        (i + 1, ItemSet::intersection(halves))
+    });
+    total(compartments, params)
+}
+
+#[allow(dead_code)]
+pub fn part_2() -> Result<u64, Rejection> {
+    part_2_from_str(&std::fs::read_to_string("input/2022/real/3.txt").unwrap(), &Params::default())
+}
+
+pub fn part_2_from_str(input: &str, params: &Params) -> Result<u64, Rejection> {
+    assert!(params.group_size > 0, "a group needs at least one elf");
+    let priorities = &params.priorities;
+    let rucksacks = rucksacks_from_str(input, priorities)?;
+    let groups: Vec<(usize, ItemSet)> = rucksacks.chunks(params.group_size).enumerate()
+        .map(|(i, group)| {
+            let line = i * params.group_size + 1;
+            if group.len() < params.group_size {
+                return Err(Rejection::Short { line, elves: group.len() });
+            }
+            Ok((line, ItemSet::intersection(group.iter().map(|rucksack| ItemSet::from_places(rucksack, priorities)))))
+        })
+        .collect::<Result<_, _>>()?;
+    total(groups.into_iter(), params)
+}
+
+// `size` groups of three elves. Each rucksack has exactly one item in both halves and each group exactly one badge,
+// because the three rucksacks of a group draw everything but the badge from their own third of the alphabet
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    let mut input = String::new();
+    for _ in 0..size {
+        rng.shuffle(&mut items);
+        let (&badge, rest) = items.split_first().unwrap();
+        for own_items in rest.chunks(17) {
+            let (&shared, own_items) = own_items.split_first().unwrap();
+            let (left_items, right_items) = own_items.split_at(8);
+            let half = rng.range(2..=16) as usize;
+            let mut left = vec![shared, badge];
+            let mut right = vec![shared];
+            while left.len() < half { left.push(*rng.choose(left_items)); }
+            while right.len() < half { right.push(*rng.choose(right_items)); }
+            rng.shuffle(&mut left);
+            rng.shuffle(&mut right);
+            input.extend(left.into_iter().chain(right));
+            input.push('\n');
+        }
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::check::differential;
+
+    // The sets the long way round, taking whichever shared item comes first
+    fn reference(input: &str, group_size: usize) -> Option<u64> {
+        let priority = |c: char| if c.is_lowercase() { c as u64 - 'a' as u64 + 1 } else { c as u64 - 'A' as u64 + 27 };
+        let rucksacks: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        Some(rucksacks.chunks(group_size)
+            .map(|group| group.iter()
+                .map(|rucksack| BTreeSet::from_iter(rucksack.iter().copied()))
+                .reduce(|a, b| &a & &b).unwrap()
+                .into_iter().next().unwrap())
+            .map(priority)
+            .sum())
+    }
+
+    #[test]
+    fn masks_agree_with_sets() {
+        let sample = std::fs::read_to_string("input/2022/sample/3.txt").unwrap();
+        assert_eq!(part_1_from_str(&sample, &Params::default()), Ok(157));
+        assert_eq!(part_2_from_str(&sample, &Params::default()), Ok(70));
+        differential(generate, 0..=20, 100, |i| part_2_from_str(i, &Params::default()).unwrap(), |i| reference(i, 3));
+        let halves = |input: &str| input.lines()
+            .flat_map(|line| { let (a, b) = line.split_at(line.len() / 2); [a.to_owned(), b.to_owned()] })
+            .collect::<Vec<_>>()
+            .join("\n");
+        differential(generate, 0..=20, 100, |i| part_1_from_str(i, &Params::default()).unwrap(), |i| reference(&halves(i), 2));
+    }
+
+    #[test]
+    fn exactly_one_shared_item() {
+        let params = Params::default();
+        assert_eq!(part_1_from_str("abAa\nabcd\n", &params), Err(Rejection::NothingShared { line: 2 }));
+        assert_eq!(part_1_from_str("abBAba\n", &params), Err(Rejection::SharedSeveral { line: 1, items: vec!['a', 'b'] }));
+        assert_eq!(part_2_from_str("ab\nbc\nbd\nxy\n", &params), Err(Rejection::Short { line: 4, elves: 1 }));
+        assert_eq!(
+            part_2_from_str("aZb\nbZa\nZab\n", &params).unwrap_err().to_string(),
+            "line 1: abZ in common",
+        );
+
+        let all = Params { shared: Shared::All, ..Params::default() };
+        assert_eq!(part_1_from_str("abAa\nabBAba\n", &all), Ok(1 + (1 + 2)));
+        let pairs = Params { group_size: 2, shared: Shared::All, ..Params::default() };
+        assert_eq!(part_2_from_str("aZb\nbZa\nZab\nyx\n", &pairs), Ok(1 + 2 + 52));
+        let letters = PriorityTable::letters();
+        let set = ItemSet::from_places(&['Z', 'a', 'z', 'A'].map(|c| letters.place(c).unwrap()), &letters);
+        assert_eq!(set.places().map(|place| letters.item(place)).collect::<String>(), "azAZ");
+    }
+
+    #[test]
+    fn custom_priority_tables() {
+        let table = PriorityTable::from_table("# digits, then a few more\n0-9 1\nß 100\n🎁 1000\n\u{10ffff} 5\n").unwrap();
+        assert_eq!((table.size(), table.place('🎁'), table.priority(11)), (13, Some(11), 1000));
+        let params = Params { priorities: table, ..Params::default() };
+        assert_eq!(part_1_from_str("1ß2🎁ß3\n9\u{10ffff}8\u{10ffff}77\n", &params), Ok(100 + 5));
+        assert_eq!(part_1_from_str("12\n3a\n", &params), Err(Rejection::NotAnItem { line: 2, item: 'a' }));
+        assert_eq!(part_1_from_str("1a", &Params::default()).unwrap_err().to_string(), "line 1: '1' isn't an item");
+
+        // enough items to need a second word
+        let wide = PriorityTable::from_table("\u{4e00}-\u{4e63} 1").unwrap();
+        let params = Params { priorities: wide, ..Params::default() };
+        assert_eq!(part_1_from_str("\u{4e00}\u{4e63}\u{4e10}\u{4e63}\n", &params), Ok(100));
+
+        let huge = PriorityTable::from_table(&format!("a {}\nb 1", u64::MAX)).unwrap();
+        assert_eq!(part_1_from_str("aa\nbb\n", &Params { priorities: huge, ..Params::default() }), Err(Rejection::Overflow));
+    }
+
+    #[test]
+    fn rejects_bad_tables() {
+        let error = |table: &str| PriorityTable::from_table(table).unwrap_err();
+        assert_eq!(error("a-z 1\n\nq 30"), "line 3: 'q' is in the table twice");
+        assert_eq!(error("z-a 1"), "line 1: expected an item or a range like a-z: z-a");
+        assert_eq!(error("ab 1"), "line 1: expected an item or a range like a-z: ab");
+        assert_eq!(error("a"), "line 1: expected an item or a range and a priority: a");
+        assert_eq!(error("a one"), "line 1: not a priority: one");
+        assert_eq!(error("\u{1}-\u{3} 1"), "line 1: '\\u{1}' can't be an item");
+        assert_eq!(error(&format!("a-c {}", u64::MAX - 1)), format!("line 1: 'c' would be past {}", u64::MAX));
+    }
+}
@@ -0,0 +1,339 @@
+use std::{cmp::Ordering::{Equal, Greater, Less}, fmt};
+
+use crate::{generate::Rng, input::Input};
+
+fn pairs_from_str(input: &str) -> Vec<((i32, i32), (i32, i32))> {
+    let parse_pair = |s: &str| -> (i32, i32) {
+        let (a0, a1) = s.split_once("-").unwrap();
+        (str::parse(a0).unwrap(), str::parse(a1).unwrap())
+    };
+    Input::new(input).lines()
+        .map(|line| {
+            let (a, b) = line.split_once(",").unwrap();
+            (parse_pair(a), parse_pair(b))
+        }).collect()
+}
+
+// Allen's interval relations, each saying how the first assignment of a pair sits against the second. A section is a
+// unit of length, so 2-3 meets 4-5 rather than overlapping it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relation {
+    Before,
+    Meets,
+    Overlaps,
+    Starts,
+    During,
+    Finishes,
+    Equals,
+    FinishedBy,
+    Contains,
+    StartedBy,
+    OverlappedBy,
+    MetBy,
+    After,
+}
+
+impl Relation {
+    pub const ALL: [Relation; 13] = [
+        Relation::Before, Relation::Meets, Relation::Overlaps, Relation::Starts, Relation::During, Relation::Finishes,
+        Relation::Equals, Relation::FinishedBy, Relation::Contains, Relation::StartedBy, Relation::OverlappedBy,
+        Relation::MetBy, Relation::After,
+    ];
+
+    // One assignment inside the other
+    pub const NESTED: [Relation; 7] = [
+        Relation::Starts, Relation::During, Relation::Finishes, Relation::Equals,
+        Relation::FinishedBy, Relation::Contains, Relation::StartedBy,
+    ];
+
+    // At least one section in both
+    pub const SHARING: [Relation; 9] = [
+        Relation::Overlaps, Relation::Starts, Relation::During, Relation::Finishes, Relation::Equals,
+        Relation::FinishedBy, Relation::Contains, Relation::StartedBy, Relation::OverlappedBy,
+    ];
+
+    pub fn between(a: (i32, i32), b: (i32, i32)) -> Relation {
+        // half open, so that the comparisons are Allen's own
+        let (a0, a1) = (i64::from(a.0), i64::from(a.1) + 1);
+        let (b0, b1) = (i64::from(b.0), i64::from(b.1) + 1);
+        assert!(a0 < a1 && b0 < b1, "an assignment can't end before it starts");
+        match (a0.cmp(&b0), a1.cmp(&b1)) {
+            _ if a1 < b0 => Relation::Before,
+            _ if a1 == b0 => Relation::Meets,
+            _ if b1 < a0 => Relation::After,
+            _ if b1 == a0 => Relation::MetBy,
+            (Equal, Equal) => Relation::Equals,
+            (Equal, Less) => Relation::Starts,
+            (Equal, Greater) => Relation::StartedBy,
+            (Greater, Equal) => Relation::Finishes,
+            (Less, Equal) => Relation::FinishedBy,
+            (Greater, Less) => Relation::During,
+            (Less, Greater) => Relation::Contains,
+            (Less, Less) => Relation::Overlaps,
+            (Greater, Greater) => Relation::OverlappedBy,
+        }
+    }
+}
+
+// Every pair's relation, in the order they're listed
+pub struct Relations(Vec<Relation>);
+
+impl Relations {
+    // How many pairs are in any of `relations`
+    pub fn count(&self, relations: &[Relation]) -> usize {
+        self.0.iter().filter(|relation| relations.contains(relation)).count()
+    }
+
+    pub fn counts(&self) -> [(Relation, usize); 13] {
+        Relation::ALL.map(|relation| (relation, self.count(&[relation])))
+    }
+}
+
+impl fmt::Display for Relations {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (relation, count) in self.counts() {
+            writeln!(f, "{:<13} {count}", format!("{relation:?}"))?;
+        }
+        Ok(())
+    }
+}
+
+pub fn relations_from_str(input: &str) -> Relations {
+    Relations(pairs_from_str(input).into_iter().map(|(a, b)| Relation::between(a, b)).collect())
+}
+
+#[allow(dead_code)]
+pub fn part_1() -> usize {
+    part_1_from_str(&std::fs::read_to_string("input/2022/real/4.txt").unwrap())
+}
+
+pub fn part_1_from_str(input: &str) -> usize {
+    relations_from_str(input).count(&Relation::NESTED)
+}
+
+#[allow(dead_code)]
+pub fn part_2() -> usize {
+    part_2_from_str(&std::fs::read_to_string("input/2022/real/4.txt").unwrap())
+}
+
+pub fn part_2_from_str(input: &str) -> usize {
+    relations_from_str(input).count(&Relation::SHARING)
+}
+
+// Every elf's assignment, both of each pair in the order they're listed
+fn elves_from_str(input: &str) -> Vec<(i64, i64)> {
+    pairs_from_str(input).into_iter()
+        .flat_map(|(a, b)| [a, b])
+        .map(|(first, last)| (first.into(), last.into()))
+        .collect()
+}
+
+// The camp as a whole rather than pair by pair. Sections are only counted from the lowest anyone has to the highest,
+// and elves are numbered from 0 in the order they're listed
+pub struct Coverage {
+    pub elves: usize,
+    pub span: Option<(i64, i64)>,
+    // the most elves on any one section, and the first section with that many
+    pub deepest: (usize, i64),
+    pub uncovered: Vec<(i64, i64)>,
+    pub covered_once: Vec<(i64, i64)>,
+    // as few elves as still cover every section someone does, so that all the others could go
+    pub cover: Vec<usize>,
+}
+
+// A sweep over where assignments start and stop, with the depth between one event and the next constant
+pub fn coverage(elves: &[(i64, i64)]) -> Coverage {
+    let mut events: Vec<(i64, i64)> = elves.iter().flat_map(|&(first, last)| [(first, 1), (last + 1, -1)]).collect();
+    events.sort_unstable();
+
+    let mut depth = 0;
+    let mut deepest = (0, 0);
+    let mut uncovered = vec![];
+    let mut covered_once = vec![];
+    for (i, &(section, change)) in events.iter().enumerate() {
+        depth += change;
+        let Some(&(next, _)) = events.get(i + 1) else { break; };
+        if next == section {
+            continue;
+        }
+        match depth {
+            0 => uncovered.push((section, next - 1)),
+            1 => covered_once.push((section, next - 1)),
+            _ => {}
+        }
+        if depth as usize > deepest.0 {
+            deepest = (depth as usize, section);
+        }
+    }
+    let span = events.first().zip(events.last()).map(|(&(first, _), &(end, _))| (first, end - 1));
+    Coverage { elves: elves.len(), span, deepest, uncovered, covered_once, cover: cover(elves) }
+}
+
+// Greedy, left to right: of the elves starting no later than the first section still to cover, keep the one reaching
+// furthest. Where nobody starts in time there's a gap, and the next elf to start picks up after it
+fn cover(elves: &[(i64, i64)]) -> Vec<usize> {
+    let mut by_start: Vec<usize> = (0..elves.len()).collect();
+    by_start.sort_unstable_by_key(|&elf| elves[elf]);
+    let mut cover = vec![];
+    let mut next = i64::MIN;
+    let mut i = 0;
+    while i < by_start.len() {
+        next = next.max(elves[by_start[i]].0);
+        let mut furthest: Option<usize> = None;
+        while i < by_start.len() && elves[by_start[i]].0 <= next {
+            let elf = by_start[i];
+            if furthest.is_none_or(|best| elves[elf].1 > elves[best].1) {
+                furthest = Some(elf);
+            }
+            i += 1;
+        }
+        let elf = furthest.unwrap();
+        if elves[elf].1 >= next {
+            cover.push(elf);
+            next = elves[elf].1 + 1;
+        }
+    }
+    cover.sort_unstable();
+    cover
+}
+
+fn sections(ranges: &[(i64, i64)]) -> i64 {
+    ranges.iter().map(|(first, last)| last - first + 1).sum()
+}
+
+impl fmt::Display for Coverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Some((first, last)) = self.span else {
+            return writeln!(f, "no elves");
+        };
+        let sections = |ranges: &[(i64, i64)]| match sections(ranges) {
+            0 => "0 sections".to_owned(),
+            n => format!("{n} sections: {}", ranges.iter()
+                .map(|&(first, last)| if first == last { first.to_string() } else { format!("{first}-{last}") })
+                .collect::<Vec<_>>()
+                .join(", ")),
+        };
+        writeln!(f, "{} elves on sections {first}-{last}", self.elves)?;
+        writeln!(f, "at most {} elves on one section, first on {}", self.deepest.0, self.deepest.1)?;
+        writeln!(f, "nobody on {}", sections(&self.uncovered))?;
+        writeln!(f, "one elf on {}", sections(&self.covered_once))?;
+        let cover: Vec<String> = self.cover.iter().map(usize::to_string).collect();
+        writeln!(f, "elves {} cover as much as everyone, so the other {} could go", cover.join(", "), self.elves - self.cover.len())
+    }
+}
+
+pub fn coverage_from_str(input: &str) -> Coverage {
+    coverage(&elves_from_str(input))
+}
+
+// `size` pairs of section assignments
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut assignment = || {
+        let (a, b) = (rng.range(1..=99), rng.range(1..=99));
+        format!("{}-{}", a.min(b), a.max(b))
+    };
+    (0..size).map(|_| format!("{},{}\n", assignment(), assignment())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::differential;
+
+    // Section by section: the deepest, how many sections have nobody and how many one elf, and the size of the smallest
+    // set of elves covering everything, trying every set
+    fn reference(input: &str) -> Option<(usize, i64, i64, usize)> {
+        let elves = elves_from_str(input);
+        let covered = |elves: &[(i64, i64)], section: i64| elves.iter().filter(|&&(a, b)| a <= section && section <= b).count();
+        let first = elves.iter().map(|e| e.0).min()?;
+        let last = elves.iter().map(|e| e.1).max()?;
+        let depths: Vec<usize> = (first..=last).map(|section| covered(&elves, section)).collect();
+        let count = |depth: usize| depths.iter().filter(|&&d| d == depth).count() as i64;
+        let smallest = (0u32..1 << elves.len())
+            .filter(|subset| {
+                let kept: Vec<(i64, i64)> = (0..elves.len()).filter(|i| subset & 1 << i != 0).map(|i| elves[i]).collect();
+                (first..=last).all(|section| (covered(&kept, section) > 0) == (covered(&elves, section) > 0))
+            })
+            .map(u32::count_ones)
+            .min()? as usize;
+        Some((*depths.iter().max()?, count(0), count(1), smallest))
+    }
+
+    #[test]
+    fn sweep_agrees_with_counting_sections() {
+        let fast = |input: &str| {
+            let coverage = coverage_from_str(input);
+            let elves = elves_from_str(input);
+            let kept: Vec<(i64, i64)> = coverage.cover.iter().map(|&elf| elves[elf]).collect();
+            let sizes = (coverage.deepest.0, sections(&coverage.uncovered), sections(&coverage.covered_once), kept.len());
+            // a cover that misses something only shows up as too small, so check it covers the same sections too
+            assert_eq!(coverage_from_str(&kept.iter().map(|(a, b)| format!("{a}-{b},{a}-{b}\n")).collect::<String>()).uncovered, coverage.uncovered);
+            sizes
+        };
+        differential(generate, 1..=4, 200, fast, reference);
+    }
+
+    #[test]
+    fn sample_coverage() {
+        let sample = std::fs::read_to_string("input/2022/sample/4.txt").unwrap();
+        let coverage = coverage_from_str(&sample);
+        assert_eq!((coverage.elves, coverage.span, coverage.deepest), (12, Some((2, 9)), (8, 6)));
+        assert_eq!((coverage.uncovered, coverage.covered_once), (vec![], vec![(9, 9)]));
+        assert_eq!(coverage.cover, [5, 6]);
+        assert_eq!(coverage_from_str("").to_string(), "no elves\n");
+    }
+
+    // How the second assignment sits against the first: ALL lists each relation opposite its inverse
+    fn inverse(relation: Relation) -> Relation {
+        Relation::ALL[Relation::ALL.len() - 1 - relation as usize]
+    }
+
+    // The conditions the parts used to check by hand
+    fn reference_part_1(input: &str) -> Option<usize> {
+        Some(pairs_from_str(input).into_iter()
+            .filter(|((a0, a1), (b0, b1))| (a0 >= b0 && a1 <= b1) || (a0 <= b0 && a1 >= b1))
+            .count())
+    }
+
+    fn reference_part_2(input: &str) -> Option<usize> {
+        Some(pairs_from_str(input).into_iter()
+            .filter(|((a0, a1), (b0, b1))|
+                (a0 >= b0 && a0 <= b1) || (a1 >= b0 && a1 <= b1) || (b0 >= a0 && b0 <= a1) || (b1 >= a0 && b1 <= a1)
+            )
+            .count())
+    }
+
+    #[test]
+    fn relations_filter_into_the_parts() {
+        let sample = std::fs::read_to_string("input/2022/sample/4.txt").unwrap();
+        let relations = relations_from_str(&sample);
+        assert_eq!(
+            relations.0,
+            [Relation::Before, Relation::Meets, Relation::Overlaps, Relation::Contains, Relation::Finishes, Relation::Overlaps],
+        );
+        assert_eq!((part_1_from_str(&sample), part_2_from_str(&sample)), (2, 4));
+        assert_eq!(relations.counts().iter().map(|&(_, count)| count).sum::<usize>(), 6);
+        differential(generate, 0..=30, 200, part_1_from_str, reference_part_1);
+        differential(generate, 0..=30, 200, part_2_from_str, reference_part_2);
+    }
+
+    // All 13 turn up among small assignments, each pair has exactly one, and swapping the pair inverts it
+    #[test]
+    fn thirteen_relations() {
+        let assignments: Vec<(i32, i32)> = (1..=4).flat_map(|first| (first..=4).map(move |last| (first, last))).collect();
+        let mut seen = vec![];
+        for &a in &assignments {
+            for &b in &assignments {
+                let relation = Relation::between(a, b);
+                assert_eq!(Relation::between(b, a), inverse(relation));
+                assert_eq!(relation == Relation::Equals, a == b);
+                if !seen.contains(&relation) {
+                    seen.push(relation);
+                }
+            }
+        }
+        assert_eq!(seen.len(), 13);
+        assert_eq!(Relation::between((i32::MIN, i32::MAX), (i32::MAX, i32::MAX)), Relation::FinishedBy);
+        assert!(relations_from_str("1-2,3-4\n1-2,4-5\n").to_string().starts_with("Before        1\nMeets         1\n"));
+    }
+}
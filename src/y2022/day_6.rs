@@ -0,0 +1,217 @@
+use std::{fs::File, io::{self, BufRead, BufReader, Read}};
+
+use crate::{cancel::Unsolved, generate::Rng};
+
+const BOM: &[u8] = "\u{feff}".as_bytes();
+
+// Every marker in a datastream, as the number of bytes read by the end of it: anywhere the last `size` bytes are all
+// different. Reads as it goes, so the stream can be as long as it likes, and stops at the end of the first line or
+// at the first read error
+pub struct Markers<R> {
+    reader: BufReader<R>,
+    started: bool,
+    size: usize,
+    // the last `size` bytes, going round
+    window: Vec<u8>,
+    counts: [usize; 256],
+    distinct: usize,
+    read: usize,
+    ended: bool,
+}
+
+pub fn markers<R: Read>(reader: R, size: usize) -> Markers<R> {
+    // there are only 256 different bytes, so anything longer can never be a marker, and a marker needs at least one
+    let ended = size == 0 || size > 256;
+    let window = vec![0; if ended { 0 } else { size }];
+    Markers { reader: BufReader::new(reader), started: false, size, window, counts: [0; 256], distinct: 0, read: 0, ended }
+}
+
+impl<R: Read> Markers<R> {
+    fn skip_bom(&mut self) -> io::Result<()> {
+        if self.reader.fill_buf()?.starts_with(BOM) {
+            self.reader.consume(BOM.len());
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for Markers<R> {
+    type Item = io::Result<usize>;
+
+    fn next(&mut self) -> Option<io::Result<usize>> {
+        if self.ended {
+            return None;
+        }
+        // nothing is read until the first marker is asked for
+        if !self.started {
+            self.started = true;
+            if let Err(e) = self.skip_bom() {
+                self.ended = true;
+                return Some(Err(e));
+            }
+        }
+        for byte in (&mut self.reader).bytes() {
+            let byte = match byte {
+                Ok(byte) => byte,
+                Err(e) => {
+                    self.ended = true;
+                    return Some(Err(e));
+                }
+            };
+            if byte == b'\n' || byte == b'\r' {
+                break;
+            }
+            let slot = &mut self.window[self.read % self.size];
+            if self.read >= self.size {
+                self.counts[*slot as usize] -= 1;
+                if self.counts[*slot as usize] == 0 {
+                    self.distinct -= 1;
+                }
+            }
+            *slot = byte;
+            self.counts[byte as usize] += 1;
+            if self.counts[byte as usize] == 1 {
+                self.distinct += 1;
+            }
+            self.read += 1;
+            if self.distinct == self.size {
+                return Some(Ok(self.read));
+            }
+        }
+        self.ended = true;
+        None
+    }
+}
+
+// None if there's no run of `marker_size` different characters anywhere
+pub fn find_marker(reader: impl Read, marker_size: usize) -> io::Result<Option<usize>> {
+    markers(reader, marker_size).next().transpose()
+}
+
+pub fn real_input() -> File {
+    File::open("input/2022/real/6.txt").unwrap()
+}
+
+#[allow(dead_code)]
+pub fn part_1() -> Result<usize, Unsolved> {
+    find_marker(real_input(), 4).unwrap().ok_or(Unsolved::NoSolution)
+}
+
+pub fn part_1_from_str(input: &str) -> Result<usize, Unsolved> {
+    find_marker(input.as_bytes(), 4).unwrap().ok_or(Unsolved::NoSolution)
+}
+
+#[allow(dead_code)]
+pub fn part_2() -> Result<usize, Unsolved> {
+    find_marker(real_input(), 14).unwrap().ok_or(Unsolved::NoSolution)
+}
+
+pub fn part_2_from_str(input: &str) -> Result<usize, Unsolved> {
+    find_marker(input.as_bytes(), 14).unwrap().ok_or(Unsolved::NoSolution)
+}
+
+// `size` characters drawn from too few letters to hold a start-of-message marker, with one at the very end
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut stream: String = (0..size).map(|_| (b'a' + rng.below(10) as u8) as char).collect();
+    let mut marker: Vec<char> = ('a'..='z').collect();
+    rng.shuffle(&mut marker);
+    stream.extend(&marker[..14]);
+    stream + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::check::differential;
+
+    // Check every window from scratch
+    fn reference(chars: &[u8], marker_size: usize) -> Vec<usize> {
+        chars.windows(marker_size).enumerate()
+            .filter(|(_, window)| HashSet::<u8>::from_iter(window.iter().copied()).len() == marker_size)
+            .map(|(index, _)| index + marker_size)
+            .collect()
+    }
+
+    // Hands over a byte at a time, so every window straddles reads
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let Some((&first, rest)) = self.0.split_first() else { return Ok(0); };
+            buf[0] = first;
+            self.0 = rest;
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn find_marker_matches_checking_every_window() {
+        for marker_size in [1, 2, 4, 9, 14] {
+            differential(
+                generate,
+                0..=300,
+                100,
+                |input| find_marker(input.trim().as_bytes(), marker_size).unwrap(),
+                |input| Some(reference(input.trim().as_bytes(), marker_size).first().copied()),
+            );
+            differential(
+                generate,
+                0..=300,
+                50,
+                |input| markers(Trickle(input.as_bytes()), marker_size).collect::<io::Result<Vec<_>>>().unwrap(),
+                |input| Some(reference(input.trim().as_bytes(), marker_size)),
+            );
+        }
+    }
+
+    #[test]
+    fn streams_every_marker() {
+        let sample = std::fs::read_to_string("input/2022/sample/6.txt").unwrap();
+        assert_eq!((part_1_from_str(&sample), part_2_from_str(&sample)), (Ok(7), Ok(19)));
+        let all = |reader, size| markers(reader, size).collect::<io::Result<Vec<_>>>().unwrap();
+        assert_eq!(all("abcabd\nxyz".as_bytes(), 3), [3, 4, 5, 6]);
+        assert_eq!(all("\u{feff}aab\r\n".as_bytes(), 2), [3]);
+        assert_eq!(part_1_from_str("abcabcabc\n"), Err(Unsolved::NoSolution));
+        assert!(markers("".as_bytes(), 1).next().is_none());
+
+        let every_byte: Vec<u8> = (0..=255).filter(|&b| b != b'\n' && b != b'\r').chain([b'\n', b'\r']).collect();
+        assert_eq!(find_marker(&every_byte[..254], 254).unwrap(), Some(254));
+        assert_eq!(find_marker(&every_byte[..], 257).unwrap(), None);
+        assert_eq!(find_marker(&every_byte[..], 0).unwrap(), None);
+
+        // a marker in the middle of a stream far bigger than anything we hold on to
+        let a_lot = || io::repeat(b'a').take(10_000_000);
+        let long = a_lot().chain(&b"bcd"[..]).chain(a_lot());
+        assert_eq!(markers(long, 4).collect::<io::Result<Vec<_>>>().unwrap(), [10_000_003, 10_000_004]);
+    }
+
+    // Hands over what it has, then fails
+    struct Unplugged<'a>(&'a [u8]);
+
+    impl Read for Unplugged<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() {
+                return Err(io::Error::other("unplugged"));
+            }
+            self.0.read(buf)
+        }
+    }
+
+    #[test]
+    fn read_errors_end_the_markers() {
+        let mut stream = markers(Unplugged(b"abab"), 2);
+        assert_eq!(stream.next().map(|marker| marker.ok()), Some(Some(2)));
+        assert_eq!(stream.next().map(|marker| marker.ok()), Some(Some(3)));
+        assert_eq!(stream.next().map(|marker| marker.ok()), Some(Some(4)));
+        assert_eq!(stream.next().map(|marker| marker.map_err(|e| e.to_string())), Some(Err("unplugged".to_owned())));
+        assert!(stream.next().is_none());
+        // a stream that's ended from the start never gets read
+        assert!(markers(Unplugged(b""), 0).next().is_none());
+        // the byte order mark is only looked for once a marker is asked for
+        let mut stream = markers(Unplugged(b""), 2);
+        assert_eq!(stream.next().map(|marker| marker.map_err(|e| e.kind())), Some(Err(io::ErrorKind::Other)));
+        assert!(stream.next().is_none());
+    }
+}
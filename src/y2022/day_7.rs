@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+
+use crate::{cancel::Unsolved, generate::Rng, input::Input, params::params};
+
+params! {
+    small_directory_max: u64 = 100_000, 100_000;
+    disk_size: u64 = 70_000_000, 70_000_000;
+    space_needed: u64 = 30_000_000, 30_000_000;
+}
+
+#[derive(Debug)]
+enum FileInfo {
+    Directory(String),
+    File(String, u64),
+}
+
+#[derive(Debug)]
+enum Command {
+    Ls(Vec<FileInfo>),
+    CdRoot,
+    CdUp,
+    Cd(String),
+}
+
+#[derive(Debug)]
+enum FileSystem {
+    Directory(HashMap<String, FileSystem>),
+    File(u64),
+}
+
+fn output_from_str(input: &str) -> Vec<Command> {
+    Input::new(input).lines().fold(Vec::new(), |mut list, line| {
+        if line == "$ ls" {
+            list.push(Command::Ls(vec![]));
+        } else if line == "$ cd /" {
+            list.push(Command::CdRoot);
+        } else if line == "$ cd .." {
+            list.push(Command::CdUp);
+        } else if let Some(dir_name) = line.strip_prefix("$ cd ") {
+            list.push(Command::Cd(dir_name.to_owned()))
+        } else {
+            let Command::Ls(mut files) = list.pop().unwrap() else { unreachable!(); };
+            let info = if let Some(dir_name) = line.strip_prefix("dir ") {
+                FileInfo::Directory(dir_name.to_owned())
+            } else {
+                let (size, name) = line.split_once(" ").unwrap();
+                FileInfo::File(name.to_owned(), size.parse().unwrap())
+            };
+            files.push(info);
+            list.push(Command::Ls(files));
+        }
+
+        list
+    })
+}
+
+fn merge_ls_into(mut fs: &mut FileSystem, path: &[String], new: Vec<(String, FileSystem)>) {
+    for dir in path {
+        let FileSystem::Directory(f) = fs else { unreachable!() };
+        fs = f.get_mut(dir).unwrap();
+    }
+
+    let FileSystem::Directory(target_dir) = fs else { unreachable!(); };
+
+    for (name, new_fs) in new.into_iter() {
+        target_dir.entry(name).and_modify(|_| ()).or_insert(new_fs);
+    }
+}
+
+fn filesystem_from_output(commands: Vec<Command>) -> FileSystem {
+    let mut path: Vec<String> = vec![];
+
+    let mut fs = FileSystem::Directory(HashMap::new());
+
+    for command in commands.into_iter() {
+        match command {
+            Command::CdRoot => { path.drain(0..); },
+            Command::CdUp => { path.pop(); },
+            Command::Cd(dir_name) => { path.push(dir_name); },
+            Command::Ls(infos) => {
+                let contents: Vec<(String, FileSystem)> = infos.into_iter().map(|info| {
+                    match info {
+                        FileInfo::Directory(name) => (name, FileSystem::Directory(HashMap::new())),
+                        FileInfo::File(name, size) => (name, FileSystem::File(size))
+                    }
+                }).collect();
+
+                merge_ls_into(&mut fs, &path, contents);
+            }
+        }
+    }
+
+    fs
+}
+
+fn all_directory_sizes_rec(sizes: &mut HashMap<Vec<String>, u64>, path: &mut Vec<String>, dir: HashMap<String, FileSystem>) -> Result<u64, Unsolved> {
+    let mut total: u64 = 0;
+    for (name, file) in dir {
+        let size = match file {
+            FileSystem::Directory(subdir) => {
+                path.push(name);
+                let size = all_directory_sizes_rec(sizes, path, subdir)?;
+                path.pop();
+                size
+            },
+            FileSystem::File(size) => size,
+        };
+        total = total.checked_add(size).ok_or(Unsolved::Overflow)?;
+    }
+    sizes.insert(path.clone(), total);
+    Ok(total)
+}
+
+// Overflow if any directory holds more than a u64 can count
+fn all_directory_sizes(fs: FileSystem) -> Result<HashMap<Vec<String>, u64>, Unsolved> {
+    let mut sizes = HashMap::new();
+    let mut path = vec![];
+    let FileSystem::Directory(dir) = fs else { unreachable!(); };
+    all_directory_sizes_rec(&mut sizes, &mut path, dir)?;
+    Ok(sizes)
+}
+
+#[allow(dead_code)]
+pub fn part_1() -> Result<u64, Unsolved> {
+    part_1_from_str(&std::fs::read_to_string("input/2022/real/7.txt").unwrap(), &Params::default())
+}
+
+pub fn part_1_from_str(input: &str, params: &Params) -> Result<u64, Unsolved> {
+    let fs = filesystem_from_output(output_from_str(input));
+    let sizes = all_directory_sizes(fs)?;
+    sizes.values().copied()
+        .filter(|&size| size <= params.small_directory_max)
+        .try_fold(0, u64::checked_add).ok_or(Unsolved::Overflow)
+}
+
+#[allow(dead_code)]
+pub fn part_2() -> Result<u64, Unsolved> {
+    part_2_from_str(&std::fs::read_to_string("input/2022/real/7.txt").unwrap(), &Params::default())
+}
+
+// NoSolution if even deleting everything doesn't free up enough
+pub fn part_2_from_str(input: &str, params: &Params) -> Result<u64, Unsolved> {
+    let fs = filesystem_from_output(output_from_str(input));
+    let sizes = all_directory_sizes(fs)?;
+    let unused_space = params.disk_size.saturating_sub(sizes[&vec![]]);
+    let to_free = params.space_needed.saturating_sub(unused_space);
+    sizes.values().copied().filter(|&size| size >= to_free).min().ok_or(Unsolved::NoSolution)
+}
+
+// A terminal session exploring `size` directories. The files add up to enough that part 2 has to delete something
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut children: Vec<Vec<usize>> = vec![vec![]];
+    for dir in 1..size.max(1) {
+        children[rng.below(dir)].push(dir);
+        children.push(vec![]);
+    }
+    // the index keeps names unique within a directory
+    let names: Vec<String> = (0..children.len()).map(|i| format!("{}{}", rng.lowercase(3), i)).collect();
+
+    // share out a disk that's between 41M and 69M full by weight
+    let weights: Vec<Vec<i64>> = (0..children.len())
+        .map(|_| (0..rng.below(6)).map(|_| rng.range(1..=1000)).collect())
+        .collect();
+    let total_weight: i64 = weights.iter().flatten().sum::<i64>().max(1);
+    let used = rng.range(41_000_000..=69_000_000);
+    let files: Vec<Vec<i64>> = weights.into_iter()
+        .map(|ws| ws.into_iter().map(|w| (used * w / total_weight).max(1)).collect())
+        .collect();
+
+    fn explore(rng: &mut Rng, children: &[Vec<usize>], names: &[String], files: &[Vec<i64>], dir: usize, output: &mut Vec<String>) {
+        output.push("$ ls".to_owned());
+        for &child in &children[dir] {
+            output.push(format!("dir {}", names[child]));
+        }
+        for (i, size) in files[dir].iter().enumerate() {
+            output.push(format!("{} {}{}.{}", size, rng.lowercase(4), i, rng.lowercase(3)));
+        }
+        for &child in &children[dir] {
+            output.push(format!("$ cd {}", names[child]));
+            explore(rng, children, names, files, child, output);
+            output.push("$ cd ..".to_owned());
+        }
+    }
+
+    let mut output = vec!["$ cd /".to_owned()];
+    explore(rng, &children, &names, &files, 0, &mut output);
+    output.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::check::differential;
+
+    // No tree: every file counts towards each directory above it, straight from the terminal output
+    fn reference(input: &str) -> Result<HashMap<Vec<String>, u64>, Unsolved> {
+        let mut sizes = HashMap::from([(vec![], 0)]);
+        let mut files_seen = HashSet::new();
+        let mut cwd: Vec<String> = vec![];
+        for line in input.lines() {
+            if line == "$ cd /" {
+                cwd.clear();
+            } else if line == "$ cd .." {
+                cwd.pop();
+            } else if let Some(dir_name) = line.strip_prefix("$ cd ") {
+                cwd.push(dir_name.to_owned());
+            } else if let Some(dir_name) = line.strip_prefix("dir ") {
+                sizes.entry([cwd.clone(), vec![dir_name.to_owned()]].concat()).or_insert(0);
+            } else if line != "$ ls" {
+                let (size, name) = line.split_once(' ').unwrap();
+                if files_seen.insert((cwd.clone(), name.to_owned())) {
+                    for depth in 0..=cwd.len() {
+                        let total: &mut u64 = sizes.entry(cwd[..depth].to_vec()).or_insert(0);
+                        *total = total.checked_add(size.parse().unwrap()).ok_or(Unsolved::Overflow)?;
+                    }
+                }
+            }
+        }
+        Ok(sizes)
+    }
+
+    #[test]
+    fn directory_sizes_match_adding_up_every_file() {
+        differential(
+            generate,
+            1..=40,
+            100,
+            |input| all_directory_sizes(filesystem_from_output(output_from_str(input))),
+            |input| Some(reference(input)),
+        );
+    }
+
+    #[test]
+    fn sizes_past_i32_are_counted() {
+        let input = "$ cd /\n$ ls\ndir a\n3000000000 b.txt\n$ cd a\n$ ls\n50000 c.txt\n";
+        let params = Params { disk_size: 5_000_000_000, space_needed: 3_000_000_000, ..Params::default() };
+        assert_eq!(part_1_from_str(input, &params), Ok(50_000));
+        assert_eq!(part_2_from_str(input, &params), Ok(3_000_050_000));
+    }
+
+    #[test]
+    fn sizes_past_u64_overflow() {
+        let big = u64::MAX / 2 + 1;
+        let input = format!("$ cd /\n$ ls\ndir a\n{big} b.txt\n$ cd a\n$ ls\n{big} c.txt\n");
+        assert_eq!(part_1_from_str(&input, &Params::default()), Err(Unsolved::Overflow));
+        assert_eq!(part_2_from_str(&input, &Params::default()), Err(Unsolved::Overflow));
+    }
+}
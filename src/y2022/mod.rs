@@ -0,0 +1,17 @@
+pub mod day_1;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_6;
+pub mod day_7;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_20;
+pub mod day_21;
+pub mod day_23;
+pub mod day_24;